@@ -0,0 +1,6 @@
+use aggregation::process_fib_aggregation;
+use strata_risc0_adapter::Risc0ZkVmEnv;
+
+fn main() {
+    process_fib_aggregation(&Risc0ZkVmEnv)
+}
@@ -0,0 +1,8 @@
+use groth16_verify::process_groth16_verification;
+use strata_risc0_adapter::Risc0ZkVmEnv;
+use strata_sp1_adapter::SP1Groth16Verifier;
+
+fn main() {
+    // Verifies SP1 Groth16 proofs inside Risc0
+    process_groth16_verification(&Risc0ZkVmEnv, &SP1Groth16Verifier)
+}
@@ -3,4 +3,6 @@ use sp1_build::build_program;
 fn main() {
     build_program("fibonacci");
     build_program("sha2-chain");
+    build_program("aggregation");
+    build_program("groth16-verify");
 }
@@ -0,0 +1,15 @@
+// These two lines are necessary for the program to properly compile.
+//
+// Under the hood, we wrap your main function with some extra code so that it behaves properly
+// inside the zkVM.
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use groth16_verify::process_groth16_verification;
+use strata_risc0_adapter::Risc0Groth16Verifier;
+use strata_sp1_adapter::Sp1ZkVmEnv;
+
+pub fn main() {
+    // Verifies Risc0 Groth16 proofs inside SP1
+    process_groth16_verification(&Sp1ZkVmEnv, &Risc0Groth16Verifier)
+}
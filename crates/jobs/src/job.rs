@@ -0,0 +1,83 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_zkvm::{ProofReceipt, ProofType, RecordedInput};
+
+/// The identifier of a job, assigned in submission order.
+pub type JobId = u64;
+
+/// The state of a job.
+///
+/// A job starts `Pending`, is executed to check its input before the costlier proving, and ends
+/// `Done` or `Failed`. A retryable failure puts the job back to `Pending` until its attempts are
+/// exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+pub enum JobState {
+    Pending,
+    Executing,
+    Proving,
+    Done,
+    Failed,
+}
+
+impl JobState {
+    /// Returns `true` if the job will not change state anymore.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done | Self::Failed)
+    }
+}
+
+/// A proving job and its progress.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Job {
+    pub id: JobId,
+    /// The name of the program to prove, which selects the host of the workers.
+    pub program: String,
+    pub input: RecordedInput,
+    pub proof_type: ProofType,
+    pub state: JobState,
+    /// The number of times the job was picked up by a worker.
+    pub attempts: u32,
+    /// The error of the last failed attempt.
+    pub last_error: Option<String>,
+    /// The proof, once the job is `Done`.
+    pub receipt: Option<ProofReceipt>,
+    /// The time before which a pending job is not retried, in milliseconds since the Unix epoch.
+    pub not_before: u64,
+    /// Creation time in milliseconds since the Unix epoch.
+    pub created_at: u64,
+    /// Time of the last state change in milliseconds since the Unix epoch.
+    pub updated_at: u64,
+}
+
+impl Job {
+    pub(crate) fn new(
+        id: JobId,
+        program: String,
+        input: RecordedInput,
+        proof_type: ProofType,
+    ) -> Self {
+        let now = now_millis();
+        Self {
+            id,
+            program,
+            input,
+            proof_type,
+            state: JobState::Pending,
+            attempts: 0,
+            last_error: None,
+            receipt: None,
+            not_before: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is after the unix epoch")
+        .as_millis() as u64
+}
@@ -0,0 +1,20 @@
+//! A persistent job system for proving zkVM programs.
+//!
+//! Jobs are submitted to a [`JobQueue`] as a program name, a [`RecordedInput`] and a
+//! [`ProofType`], and go through the states of [`JobState`]. Every transition is written to a
+//! [`JobStore`], such as the [`FileJobStore`], so that jobs survive restarts. A [`WorkerPool`]
+//! proves the queued jobs with the hosts registered for their programs, retrying retryable
+//! failures with an exponential backoff.
+//!
+//! [`RecordedInput`]: strata_zkvm::RecordedInput
+//! [`ProofType`]: strata_zkvm::ProofType
+
+mod job;
+mod queue;
+mod store;
+mod worker;
+
+pub use job::{Job, JobId, JobState};
+pub use queue::JobQueue;
+pub use store::{FileJobStore, JobStore};
+pub use worker::{is_retryable, WorkerConfig, WorkerPool};
@@ -0,0 +1,268 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Condvar, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use strata_zkvm::{ProofType, RecordedInput, ZkVmResult};
+use tracing::{info, warn};
+
+use crate::{
+    job::{now_millis, Job, JobId, JobState},
+    store::JobStore,
+};
+
+/// A persistent queue of proving jobs.
+///
+/// The queue only tracks jobs, which are proven by a [`WorkerPool`](crate::WorkerPool). It is
+/// meant to be shared in an `Arc` between the workers and the code submitting and querying jobs.
+pub struct JobQueue {
+    store: Box<dyn JobStore>,
+    jobs: Mutex<BTreeMap<JobId, Job>>,
+    changed: Condvar,
+}
+
+impl JobQueue {
+    /// Opens a queue with the jobs of `store`.
+    ///
+    /// Jobs that were executing or proving when the previous process stopped are put back to
+    /// pending. The interrupted attempt still counts toward the maximum number of attempts, so
+    /// that a job crashing the process is not retried forever.
+    pub fn open(store: impl JobStore + 'static) -> ZkVmResult<Self> {
+        let mut jobs = BTreeMap::new();
+        for mut job in store.load()? {
+            if matches!(job.state, JobState::Executing | JobState::Proving) {
+                info!(id = %job.id, attempts = job.attempts, "requeueing interrupted job");
+                job.state = JobState::Pending;
+                job.last_error = Some("attempt interrupted".to_string());
+                store.save(&job)?;
+            }
+            jobs.insert(job.id, job);
+        }
+
+        Ok(Self {
+            store: Box::new(store),
+            jobs: Mutex::new(jobs),
+            changed: Condvar::new(),
+        })
+    }
+
+    /// Submits a job proving `program` with `input` and returns its id.
+    pub fn submit(
+        &self,
+        program: impl Into<String>,
+        input: RecordedInput,
+        proof_type: ProofType,
+    ) -> ZkVmResult<JobId> {
+        let mut jobs = self.lock();
+        let id = jobs.last_key_value().map_or(1, |(id, _)| id + 1);
+        let job = Job::new(id, program.into(), input, proof_type);
+        self.store.save(&job)?;
+        jobs.insert(id, job);
+        self.changed.notify_all();
+        Ok(id)
+    }
+
+    /// Returns the job with the given id.
+    pub fn get(&self, id: JobId) -> Option<Job> {
+        self.lock().get(&id).cloned()
+    }
+
+    /// Returns the jobs in the given state, or all the jobs, in submission order.
+    pub fn list(&self, state: Option<JobState>) -> Vec<Job> {
+        self.lock()
+            .values()
+            .filter(|job| state.is_none_or(|state| job.state == state))
+            .cloned()
+            .collect()
+    }
+
+    /// Blocks until the job is finished or the timeout expires, and returns it.
+    ///
+    /// Returns `None` if there is no job with the given id.
+    pub fn wait(&self, id: JobId, timeout: Option<Duration>) -> Option<Job> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut jobs = self.lock();
+        loop {
+            let job = jobs.get(&id)?;
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if job.state.is_finished() || remaining.is_some_and(|r| r.is_zero()) {
+                return Some(job.clone());
+            }
+            jobs = match remaining {
+                Some(remaining) => {
+                    self.changed
+                        .wait_timeout(jobs, remaining)
+                        .expect("jobs lock")
+                        .0
+                }
+                None => self.changed.wait(jobs).expect("jobs lock"),
+            };
+        }
+    }
+
+    /// Waits for a pending job of a program accepted by `accepts` whose backoff has elapsed, and
+    /// marks it as executing. Returns `None` once `stop` is set.
+    pub(crate) fn claim(
+        &self,
+        accepts: impl Fn(&str) -> bool,
+        stop: &AtomicBool,
+        poll_interval: Duration,
+    ) -> Option<Job> {
+        let mut jobs = self.lock();
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return None;
+            }
+
+            let now = now_millis();
+            let pending: Vec<_> = jobs
+                .values()
+                .filter(|job| job.state == JobState::Pending && accepts(&job.program))
+                .collect();
+            let due = pending
+                .iter()
+                .find(|job| job.not_before <= now)
+                .map(|job| job.id);
+            // Wake up early for the next retry, if it is due before the next poll
+            let wait = pending
+                .iter()
+                .map(|job| Duration::from_millis(job.not_before.saturating_sub(now)))
+                .min()
+                .map_or(poll_interval, |next| next.min(poll_interval));
+
+            if let Some(id) = due {
+                let mut job = jobs[&id].clone();
+                job.state = JobState::Executing;
+                job.attempts += 1;
+                job.updated_at = now;
+                match self.store.save(&job) {
+                    Ok(()) => {
+                        jobs.insert(id, job.clone());
+                        self.changed.notify_all();
+                        return Some(job);
+                    }
+                    Err(err) => warn!(%id, %err, "failed to claim job"),
+                }
+            }
+
+            jobs = self.changed.wait_timeout(jobs, wait).expect("jobs lock").0;
+        }
+    }
+
+    /// Applies `update` to the job and saves it.
+    pub(crate) fn update(&self, id: JobId, update: impl FnOnce(&mut Job)) -> ZkVmResult<()> {
+        let mut jobs = self.lock();
+        let Some(job) = jobs.get(&id) else {
+            return Ok(());
+        };
+
+        let mut job = job.clone();
+        update(&mut job);
+        job.updated_at = now_millis();
+        self.store.save(&job)?;
+        jobs.insert(id, job);
+        self.changed.notify_all();
+        Ok(())
+    }
+
+    /// Wakes up the workers waiting for a job.
+    pub(crate) fn notify(&self) {
+        self.changed.notify_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<JobId, Job>> {
+        self.jobs.lock().expect("jobs lock")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::FileJobStore;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("strata-jobs-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_jobs_persist_across_reopen() {
+        let dir = temp_dir("reopen");
+        let queue = JobQueue::open(FileJobStore::open(&dir).unwrap()).unwrap();
+        let first = queue
+            .submit("fibonacci", RecordedInput::default(), ProofType::Core)
+            .unwrap();
+        let second = queue
+            .submit("sha2", RecordedInput::default(), ProofType::Groth16)
+            .unwrap();
+        assert_eq!((first, second), (1, 2));
+
+        // Simulate a process stopping while proving the first job
+        let stop = AtomicBool::new(false);
+        let claimed = queue
+            .claim(|program| program == "fibonacci", &stop, Duration::ZERO)
+            .unwrap();
+        assert_eq!(claimed.id, first);
+        assert_eq!(claimed.attempts, 1);
+        queue
+            .update(first, |job| job.state = JobState::Proving)
+            .unwrap();
+        drop(queue);
+
+        let queue = JobQueue::open(FileJobStore::open(&dir).unwrap()).unwrap();
+        let jobs = queue.list(None);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].state, JobState::Pending);
+        assert_eq!(jobs[0].attempts, 1);
+        assert_eq!(jobs[0].last_error.as_deref(), Some("attempt interrupted"));
+        assert_eq!(jobs[1].program, "sha2");
+        assert_eq!(jobs[1].proof_type, ProofType::Groth16);
+        assert_eq!(queue.list(Some(JobState::Pending)).len(), 2);
+        assert_eq!(queue.list(Some(JobState::Done)).len(), 0);
+
+        // Ids keep increasing after a reopen
+        let third = queue
+            .submit("fibonacci", RecordedInput::default(), ProofType::Core)
+            .unwrap();
+        assert_eq!(third, 3);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupted_job_is_quarantined() {
+        let dir = temp_dir("corrupted");
+        let store = FileJobStore::open(&dir).unwrap();
+        let queue = JobQueue::open(store.clone()).unwrap();
+        for program in ["fibonacci", "sha2"] {
+            queue
+                .submit(program, RecordedInput::default(), ProofType::Core)
+                .unwrap();
+        }
+        drop(queue);
+
+        let path = dir.join(format!("{:020}.job", 1));
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        std::fs::write(&path, bytes).unwrap();
+
+        // The other jobs still load, and the corrupted one is kept aside for inspection
+        let queue = JobQueue::open(store).unwrap();
+        let jobs = queue.list(None);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].program, "sha2");
+        assert!(!path.exists());
+        assert!(path.with_extension("corrupt").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
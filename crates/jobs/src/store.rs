@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use strata_zkvm::{ZkVmError, ZkVmResult};
+use tracing::warn;
+
+use crate::job::{Job, JobId};
+
+/// Persistent storage of the jobs of a [`JobQueue`](crate::JobQueue).
+///
+/// The queue keeps the jobs in memory and saves every job it changes, so a store only needs to
+/// load all the jobs when the queue is opened.
+pub trait JobStore: Send + Sync {
+    /// Loads all the stored jobs.
+    fn load(&self) -> ZkVmResult<Vec<Job>>;
+
+    /// Saves the job, replacing the stored job with the same id.
+    fn save(&self, job: &Job) -> ZkVmResult<()>;
+}
+
+/// A [`JobStore`] keeping each job in its own file.
+///
+/// Jobs are borsh-encoded and prefixed by the SHA-256 checksum of their encoding. Files are
+/// written to a temporary file, synced and renamed, so a crash or a power loss never leaves a
+/// partially written job behind. Jobs that fail to decode anyway are quarantined when loading:
+/// their file is renamed with the `corrupt` extension and the job is skipped.
+#[derive(Debug, Clone)]
+pub struct FileJobStore {
+    dir: PathBuf,
+}
+
+impl FileJobStore {
+    /// Creates a store keeping its jobs in `dir`, creating the directory if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> ZkVmResult<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(store_error)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn job_path(&self, id: JobId) -> PathBuf {
+        self.dir.join(format!("{id:020}.job"))
+    }
+}
+
+impl JobStore for FileJobStore {
+    fn load(&self) -> ZkVmResult<Vec<Job>> {
+        let mut jobs = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(store_error)? {
+            let path = entry.map_err(store_error)?.path();
+            if path.extension().is_some_and(|ext| ext == "job") {
+                let bytes = fs::read(&path).map_err(store_error)?;
+                match decode_job(&bytes) {
+                    Ok(job) => jobs.push(job),
+                    Err(err) => {
+                        let corrupt_path = path.with_extension("corrupt");
+                        warn!(path = %path.display(), %err, "quarantining undecodable job");
+                        fs::rename(&path, &corrupt_path).map_err(store_error)?;
+                    }
+                }
+            }
+        }
+        Ok(jobs)
+    }
+
+    fn save(&self, job: &Job) -> ZkVmResult<()> {
+        let payload = borsh::to_vec(job).map_err(store_error)?;
+        let bytes = [Sha256::digest(&payload).as_slice(), &payload].concat();
+
+        let path = self.job_path(job.id);
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path).map_err(store_error)?;
+        file.write_all(&bytes).map_err(store_error)?;
+        file.sync_all().map_err(store_error)?;
+        fs::rename(&tmp_path, &path).map_err(store_error)?;
+        sync_dir(&self.dir)
+    }
+}
+
+/// Syncs a directory, so that the files renamed in it survive a power loss.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> ZkVmResult<()> {
+    fs::File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(store_error)
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> ZkVmResult<()> {
+    Ok(())
+}
+
+fn decode_job(bytes: &[u8]) -> Result<Job, String> {
+    if bytes.len() < 32 {
+        return Err("truncated job".to_string());
+    }
+    let (checksum, payload) = bytes.split_at(32);
+    if Sha256::digest(payload).as_slice() != checksum {
+        return Err("checksum mismatch".to_string());
+    }
+    borsh::from_slice(payload).map_err(|e| e.to_string())
+}
+
+fn store_error(err: impl std::fmt::Display) -> ZkVmError {
+    ZkVmError::Other(format!("job store: {err}"))
+}
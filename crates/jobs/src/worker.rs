@@ -0,0 +1,361 @@
+use std::{
+    collections::HashMap,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use strata_zkvm::{DynZkVmHost, ProofReceipt, ZkVmError, ZkVmResult};
+use tracing::{info, warn};
+
+use crate::{
+    job::{now_millis, Job, JobState},
+    queue::JobQueue,
+};
+
+/// The configuration of a [`WorkerPool`].
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// The number of jobs proven concurrently.
+    pub workers: usize,
+    /// The number of attempts after which a job failing with a retryable error is failed.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled on every following retry.
+    pub base_backoff: Duration,
+    /// The maximum delay between retries.
+    pub max_backoff: Duration,
+    /// How often idle workers check for jobs that were not notified, e.g. after a retry delay.
+    pub poll_interval: Duration,
+    /// Decides whether a failed attempt is retried. Defaults to [`is_retryable`].
+    pub retry_if: fn(&ZkVmError) -> bool,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            workers: 1,
+            max_attempts: 3,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(1),
+            retry_if: is_retryable,
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Returns the delay before retrying a job that failed its `attempts`-th attempt.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Returns `true` for the errors that may not happen again on a new attempt.
+///
+/// Proof generation errors, which include network and prover backend failures, and other errors
+/// are retryable. Errors caused by the program, its input or its keys are not, since retrying
+/// would fail the same way.
+pub fn is_retryable(err: &ZkVmError) -> bool {
+    matches!(
+        err,
+        ZkVmError::ProofGenerationError(_) | ZkVmError::Other(_)
+    )
+}
+
+/// A pool of threads proving the jobs of a [`JobQueue`].
+///
+/// Each worker claims the oldest pending job whose program has a registered host, executes it to
+/// check the input, proves it and records the proof or the error in the queue. Jobs of programs
+/// without a host stay pending, so several pools can serve different programs of the same queue.
+pub struct WorkerPool {
+    queue: Arc<JobQueue>,
+    stop: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    /// Starts the workers, proving the jobs of each program with the host registered under its
+    /// name in `hosts`.
+    pub fn start(
+        queue: Arc<JobQueue>,
+        hosts: HashMap<String, Arc<dyn DynZkVmHost>>,
+        config: WorkerConfig,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let hosts = Arc::new(hosts);
+        let config = Arc::new(config);
+
+        let threads = (0..config.workers.max(1))
+            .map(|_| {
+                let queue = queue.clone();
+                let stop = stop.clone();
+                let hosts = hosts.clone();
+                let config = config.clone();
+                thread::spawn(move || run_worker(&queue, &hosts, &config, &stop))
+            })
+            .collect();
+
+        Self {
+            queue,
+            stop,
+            threads,
+        }
+    }
+
+    /// Stops the workers, waiting for the jobs being proven to finish.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.queue.notify();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_worker(
+    queue: &JobQueue,
+    hosts: &HashMap<String, Arc<dyn DynZkVmHost>>,
+    config: &WorkerConfig,
+    stop: &AtomicBool,
+) {
+    while let Some(job) = queue.claim(
+        |program| hosts.contains_key(program),
+        stop,
+        config.poll_interval,
+    ) {
+        // Interrupted attempts count too, so that a job crashing the process is not retried
+        // forever
+        if job.attempts > config.max_attempts {
+            warn!(id = %job.id, "job exhausted its attempts");
+            let res = queue.update(job.id, |job| {
+                job.state = JobState::Failed;
+                job.attempts = config.max_attempts;
+            });
+            if let Err(err) = res {
+                warn!(id = %job.id, %err, "failed to record job result");
+            }
+            continue;
+        }
+
+        let host = &hosts[&job.program];
+        info!(id = %job.id, %host, attempt = job.attempts, "running job");
+
+        // A panicking host fails the job instead of taking the worker down
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| run_job(queue, &**host, &job)))
+            .unwrap_or_else(|_| Err(ZkVmError::Other("host panicked".to_string())));
+
+        let res = match res {
+            Ok(receipt) => queue.update(job.id, |job| {
+                job.state = JobState::Done;
+                job.receipt = Some(receipt);
+                job.last_error = None;
+            }),
+            Err(err) => {
+                let retry = (config.retry_if)(&err) && job.attempts < config.max_attempts;
+                warn!(id = %job.id, %err, retry, "job attempt failed");
+                let not_before = now_millis() + config.backoff(job.attempts).as_millis() as u64;
+                queue.update(job.id, |job| {
+                    job.last_error = Some(err.to_string());
+                    if retry {
+                        job.state = JobState::Pending;
+                        job.not_before = not_before;
+                    } else {
+                        job.state = JobState::Failed;
+                    }
+                })
+            }
+        };
+        if let Err(err) = res {
+            warn!(id = %job.id, %err, "failed to record job result");
+        }
+    }
+}
+
+fn run_job(queue: &JobQueue, host: &dyn DynZkVmHost, job: &Job) -> ZkVmResult<ProofReceipt> {
+    // Executing first rejects invalid inputs before the much costlier proving
+    host.execute_recorded(&job.input)?;
+    queue.update(job.id, |job| job.state = JobState::Proving)?;
+    host.prove_recorded(&job.input, job.proof_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, sync::atomic::AtomicU32};
+
+    use fibonacci::process_fib;
+    use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
+    use strata_zkvm::{ProofType, RecordedInput, ZkVmHost, ZkVmInputBuilder, ZkVmInputRecorder};
+
+    use super::*;
+    use crate::FileJobStore;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("strata-jobs-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn open_queue(name: &str) -> Arc<JobQueue> {
+        Arc::new(JobQueue::open(FileJobStore::open(temp_dir(name)).unwrap()).unwrap())
+    }
+
+    fn fib_input(n: u32) -> RecordedInput {
+        ZkVmInputRecorder::new()
+            .write_serde(&n)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    /// A host running the fibonacci program, which fails with `err` the first `failures` times.
+    fn flaky_host(failures: u32, err: fn() -> ZkVmError) -> Arc<dyn DynZkVmHost> {
+        let calls = AtomicU32::new(0);
+        Arc::new(NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                if calls.fetch_add(1, Ordering::Relaxed) < failures {
+                    return Err(err());
+                }
+                process_fib(zkvm);
+                Ok(())
+            })),
+        })
+    }
+
+    fn config() -> WorkerConfig {
+        WorkerConfig {
+            workers: 2,
+            base_backoff: Duration::from_millis(1),
+            poll_interval: Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_jobs_are_proven() {
+        let queue = open_queue("proven");
+        let hosts = HashMap::from([("fibonacci".to_string(), flaky_host(0, || unreachable!()))]);
+        let _pool = WorkerPool::start(queue.clone(), hosts, config());
+
+        let ids: Vec<_> = (5..8)
+            .map(|n| {
+                queue
+                    .submit("fibonacci", fib_input(n), ProofType::Core)
+                    .unwrap()
+            })
+            .collect();
+        let unknown = queue
+            .submit("unknown", fib_input(5), ProofType::Core)
+            .unwrap();
+
+        for (id, expected) in ids.into_iter().zip([5, 8, 13]) {
+            let job = queue.wait(id, None).unwrap();
+            assert_eq!(job.state, JobState::Done);
+            assert_eq!(job.attempts, 1);
+            let receipt = job.receipt.unwrap();
+            let output: u32 =
+                NativeHost::extract_serde_public_output(receipt.public_values()).unwrap();
+            assert_eq!(output, expected);
+        }
+
+        // Jobs of programs without a host are left for other workers
+        let job = queue
+            .wait(unknown, Some(Duration::from_millis(50)))
+            .unwrap();
+        assert_eq!(job.state, JobState::Pending);
+    }
+
+    #[test]
+    fn test_retryable_errors_are_retried() {
+        let queue = open_queue("retried");
+        let err = || ZkVmError::ProofGenerationError("prover unavailable".to_string());
+        let hosts = HashMap::from([
+            ("flaky".to_string(), flaky_host(2, err)),
+            ("broken".to_string(), flaky_host(u32::MAX, err)),
+        ]);
+        let _pool = WorkerPool::start(queue.clone(), hosts, config());
+
+        let flaky = queue
+            .submit("flaky", fib_input(5), ProofType::Core)
+            .unwrap();
+        let job = queue.wait(flaky, None).unwrap();
+        assert_eq!(job.state, JobState::Done);
+        assert_eq!(job.attempts, 3);
+        assert_eq!(job.last_error, None);
+
+        let broken = queue
+            .submit("broken", fib_input(5), ProofType::Core)
+            .unwrap();
+        let job = queue.wait(broken, None).unwrap();
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(job.attempts, 3);
+        assert!(job.last_error.unwrap().contains("prover unavailable"));
+    }
+
+    #[test]
+    fn test_non_retryable_errors_fail() {
+        let queue = open_queue("failed");
+        let err = || ZkVmError::ExecutionError("invalid input".to_string());
+        let hosts = HashMap::from([("fibonacci".to_string(), flaky_host(1, err))]);
+        let _pool = WorkerPool::start(queue.clone(), hosts, config());
+
+        let id = queue
+            .submit("fibonacci", fib_input(5), ProofType::Core)
+            .unwrap();
+        let job = queue.wait(id, None).unwrap();
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(job.attempts, 1);
+        assert!(job.receipt.is_none());
+    }
+
+    #[test]
+    fn test_interrupted_attempts_count() {
+        let dir = temp_dir("interrupted");
+        let queue = JobQueue::open(FileJobStore::open(&dir).unwrap()).unwrap();
+        let id = queue
+            .submit("fibonacci", fib_input(5), ProofType::Core)
+            .unwrap();
+        drop(queue);
+
+        // Simulate a job crashing the process on every attempt
+        let stop = AtomicBool::new(false);
+        for _ in 0..config().max_attempts {
+            let queue = JobQueue::open(FileJobStore::open(&dir).unwrap()).unwrap();
+            queue.claim(|_| true, &stop, Duration::ZERO).unwrap();
+        }
+
+        let queue = Arc::new(JobQueue::open(FileJobStore::open(&dir).unwrap()).unwrap());
+        let hosts = HashMap::from([("fibonacci".to_string(), flaky_host(0, || unreachable!()))]);
+        let _pool = WorkerPool::start(queue.clone(), hosts, config());
+        let job = queue.wait(id, None).unwrap();
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(job.attempts, 3);
+        assert_eq!(job.last_error.as_deref(), Some("attempt interrupted"));
+        assert!(job.receipt.is_none());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = WorkerConfig {
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..Default::default()
+        };
+        let backoffs: Vec<_> = (1..6)
+            .map(|attempts| config.backoff(attempts).as_secs())
+            .collect();
+        assert_eq!(backoffs, [1, 2, 4, 5, 5]);
+    }
+}
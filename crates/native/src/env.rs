@@ -1,6 +1,6 @@
 use std::cell::RefCell;
 
-use strata_zkvm::{Proof, ZkVmEnv};
+use strata_zkvm::{ProgramId, Proof, ZkVmEnv};
 
 /// Encapsulates the mutable state of the NativeMachine.
 #[derive(Debug, Clone)]
@@ -78,9 +78,9 @@ impl ZkVmEnv for NativeMachine {
     ) {
     }
 
-    fn verify_native_proof(&self, _vk_digest: &[u32; 8], _public_values: &[u8]) {}
+    fn verify_native_proof(&self, _program_id: &ProgramId, _public_values: &[u8]) {}
 
-    fn read_verified_serde<T: serde::de::DeserializeOwned>(&self, _vk_digest: &[u32; 8]) -> T {
+    fn read_verified_serde<T: serde::de::DeserializeOwned>(&self, _program_id: &ProgramId) -> T {
         self.read_serde()
     }
 }
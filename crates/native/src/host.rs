@@ -1,7 +1,8 @@
 use std::{fmt, sync::Arc};
 
 use strata_zkvm::{
-    Proof, ProofReceipt, ProofType, PublicValues, VerificationKey, ZkVmError, ZkVmHost, ZkVmResult,
+    Groth16Proof, ProgramId, Proof, ProofReceipt, ProofType, PublicValues, VerificationKey,
+    ZkVmError, ZkVmHost, ZkVmProofError, ZkVmResult,
 };
 
 use crate::{env::NativeMachine, input::NativeMachineInputBuilder, proof::NativeProofReceipt};
@@ -22,21 +23,37 @@ impl ZkVmHost for NativeHost {
         native_machine: NativeMachine,
         _proof_type: ProofType,
     ) -> ZkVmResult<NativeProofReceipt> {
-        (self.process_proof)(&native_machine)?;
-        let output = native_machine.state.borrow().output.clone();
+        let public_values = self.execute(native_machine)?;
         let proof = Proof::default();
-        let public_values = PublicValues::new(output);
         Ok(ProofReceipt::new(proof, public_values).try_into()?)
     }
 
+    fn execute(&self, native_machine: NativeMachine) -> ZkVmResult<PublicValues> {
+        (self.process_proof)(&native_machine)?;
+        let output = native_machine.state.borrow().output.clone();
+        Ok(PublicValues::new(output))
+    }
+
     fn get_elf(&self) -> &[u8] {
         &[]
     }
 
+    fn backend_version(&self) -> String {
+        format!("native-{}", env!("CARGO_PKG_VERSION"))
+    }
+
     fn get_verification_key(&self) -> VerificationKey {
         VerificationKey::default()
     }
 
+    fn program_id(&self) -> ProgramId {
+        ProgramId::new([0; 8])
+    }
+
+    fn get_groth16_verification_key(&self) -> [u8; 32] {
+        [0; 32]
+    }
+
     fn extract_serde_public_output<T: serde::Serialize + serde::de::DeserializeOwned>(
         public_values_raw: &PublicValues,
     ) -> ZkVmResult<T> {
@@ -45,6 +62,11 @@ impl ZkVmHost for NativeHost {
         Ok(public_params)
     }
 
+    fn extract_groth16_proof(_receipt: &ProofReceipt) -> ZkVmResult<Groth16Proof> {
+        // Native receipts never carry a Groth16 proof
+        Err(ZkVmProofError::InvalidProofType(ProofType::Groth16).into())
+    }
+
     fn verify_inner(&self, _proof: &NativeProofReceipt) -> ZkVmResult<()> {
         Ok(())
     }
@@ -55,3 +77,42 @@ impl fmt::Display for NativeHost {
         write!(f, "native")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_zkvm::{ProofFixtures, VerificationKeySnapshot};
+
+    use super::*;
+
+    fn host() -> NativeHost {
+        NativeHost {
+            process_proof: Arc::new(Box::new(|_: &NativeMachine| Ok(()))),
+        }
+    }
+
+    #[test]
+    fn test_vk_snapshot() {
+        let dir = std::env::temp_dir().join(format!("native-fixtures-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let fixtures = ProofFixtures::new(&dir).with_update(false);
+        assert!(fixtures.check_vk_snapshot(&host()).is_err());
+
+        let snapshot = fixtures
+            .clone()
+            .with_update(true)
+            .check_vk_snapshot(&host())
+            .unwrap();
+        assert_eq!(snapshot, VerificationKeySnapshot::of(&host()));
+        assert_eq!(fixtures.check_vk_snapshot(&host()).unwrap(), snapshot);
+
+        // Any change of the keys is detected
+        let drifted = VerificationKeySnapshot {
+            groth16_verification_key: [1; 32],
+            ..snapshot
+        };
+        fixtures.save_vk_snapshot(&drifted).unwrap();
+        assert!(fixtures.check_vk_snapshot(&host()).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
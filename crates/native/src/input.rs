@@ -28,8 +28,8 @@ impl ZkVmInputBuilder<'_> for NativeMachineInputBuilder {
     }
 
     fn write_proof(&mut self, item: &AggregationInput) -> ZkVmInputResult<&mut Self> {
-        // For the native mode we only write the public values since the proof is expected to be
-        // empty
+        // For the native mode we only write the public values frame since the proof is expected
+        // to be empty
         self.write_buf(item.receipt().public_values().as_bytes())
     }
 
@@ -37,3 +37,39 @@ impl ZkVmInputBuilder<'_> for NativeMachineInputBuilder {
         Ok(self.0.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_zkvm::{ProgramId, Proof, PublicValues, VerificationKey};
+
+    use super::*;
+
+    #[test]
+    fn test_write_proof_frame_layout() {
+        let public_values = PublicValues::new(vec![1, 2, 3, 4, 5]);
+        let receipt = ProofReceipt::new(Proof::new(vec![0xaa; 16]), public_values.clone());
+        let item = AggregationInput::new(
+            receipt,
+            ProgramId::new([0xbb; 8]),
+            VerificationKey::new(vec![0xbb; 32]),
+        );
+
+        let machine = NativeMachineInputBuilder::new()
+            .write_serde(&7u32)
+            .unwrap()
+            .write_proof(&item)
+            .unwrap()
+            .write_buf(&[9, 9])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // A proof input contributes exactly one frame holding its public values.
+        let expected = vec![
+            bincode::serialize(&7u32).unwrap(),
+            public_values.into_inner(),
+            vec![9, 9],
+        ];
+        assert_eq!(machine.inputs, expected);
+    }
+}
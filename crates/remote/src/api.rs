@@ -0,0 +1,60 @@
+//! Types exchanged between the prover server and [`RemoteHost`](crate::RemoteHost).
+//!
+//! All request and response bodies are borsh-encoded. Responses are an [`ApiResult`], so that
+//! errors reach the client with their message.
+//!
+//! | Route                            | Request           | Response          |
+//! |----------------------------------|-------------------|-------------------|
+//! | `GET /programs/{name}`           |                   | [`ProgramInfo`]   |
+//! | `POST /programs/{name}/prove`    | [`ProveJob`]      | [`JobId`]         |
+//! | `POST /programs/{name}/execute`  | [`RecordedInput`] | [`PublicValues`]  |
+//! | `POST /programs/{name}/verify`   | [`ProofReceipt`]  | `()`              |
+//! | `GET /jobs/{id}`                 |                   | [`JobStatus`]     |
+//!
+//! [`PublicValues`]: strata_zkvm::PublicValues
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_zkvm::{ProgramId, ProofReceipt, ProofType, RecordedInput, VerificationKey};
+
+/// The result of an API call, with the error message if it failed.
+pub type ApiResult<T> = Result<T, String>;
+
+/// The identifier of a proving job on a prover server.
+pub type JobId = u64;
+
+/// The description of a program registered on a prover server.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProgramInfo {
+    pub elf: Vec<u8>,
+    pub verification_key: VerificationKey,
+    pub program_id: ProgramId,
+    pub groth16_verification_key: [u8; 32],
+    pub backend_version: String,
+}
+
+/// A request to prove a registered program.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProveJob {
+    pub input: RecordedInput,
+    pub proof_type: ProofType,
+}
+
+/// The status of a proving job.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum JobStatus {
+    /// The job waits for a free worker.
+    Queued,
+    /// The job is being proven.
+    Running,
+    /// The job completed with a proof.
+    Succeeded(ProofReceipt),
+    /// The job failed with the given error.
+    Failed(String),
+}
+
+impl JobStatus {
+    /// Returns `true` if the job will not change status anymore.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Succeeded(_) | Self::Failed(_))
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    fmt,
+    io::Read,
+    marker::PhantomData,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{de::DeserializeOwned, Serialize};
+use strata_zkvm::{
+    Groth16Proof, ProgramId, ProofReceipt, ProofType, PublicValues, RecordedInput, VerificationKey,
+    ZkVmError, ZkVmHost, ZkVmInputRecorder, ZkVmProofError, ZkVmResult,
+};
+
+use crate::api::{ApiResult, JobId, JobStatus, ProgramInfo, ProveJob};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A [`ZkVmHost`] proving a program registered on a [`ProverServer`](crate::ProverServer).
+///
+/// Inputs are built locally with [`ZkVmInputRecorder`] and sent to the server, which replays them
+/// into the input builder of its own host. Proving is submitted as a job whose status is polled
+/// until it finishes, while execution and verification are forwarded to the server directly.
+///
+/// `H` is the type of the host the server proves the program with, e.g. `SP1Host`. It is only
+/// used for the functions of [`ZkVmHost`] that do not take a host, such as
+/// [`ZkVmHost::extract_serde_public_output`], which depend on the zkVM that generated the
+/// proof.
+pub struct RemoteHost<H> {
+    program: Arc<RemoteProgram>,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+    _host: PhantomData<fn() -> H>,
+}
+
+struct RemoteProgram {
+    endpoint: String,
+    name: String,
+    info: ProgramInfo,
+}
+
+impl<H> Clone for RemoteHost<H> {
+    fn clone(&self) -> Self {
+        Self {
+            program: self.program.clone(),
+            poll_interval: self.poll_interval,
+            timeout: self.timeout,
+            _host: PhantomData,
+        }
+    }
+}
+
+impl<H> RemoteHost<H> {
+    /// Connects to the program registered as `name` on the server at `endpoint`.
+    pub fn connect(endpoint: impl Into<String>, name: impl Into<String>) -> ZkVmResult<Self> {
+        let endpoint = endpoint.into().trim_end_matches('/').to_string();
+        let name = name.into();
+        let info = get(&format!("{endpoint}/programs/{name}")).map_err(ZkVmError::Other)?;
+        Ok(Self {
+            program: Arc::new(RemoteProgram {
+                endpoint,
+                name,
+                info,
+            }),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: None,
+            _host: PhantomData,
+        })
+    }
+
+    /// Sets how often the status of a proving job is polled. Defaults to 500ms.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how long to wait for a proving job before failing. There is no timeout by default.
+    ///
+    /// The job is not cancelled on the server when the timeout expires.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Submits a proving job and returns its id without waiting for the proof.
+    pub fn submit(&self, input: RecordedInput, proof_type: ProofType) -> ZkVmResult<JobId> {
+        let job = ProveJob { input, proof_type };
+        post(&self.program_url("prove"), &job).map_err(ZkVmError::ProofGenerationError)
+    }
+
+    /// Returns the status of a proving job.
+    pub fn job_status(&self, id: JobId) -> ZkVmResult<JobStatus> {
+        get(&format!("{}/jobs/{id}", self.program.endpoint)).map_err(ZkVmError::Other)
+    }
+
+    /// Polls the status of a proving job until it finishes and returns its proof.
+    pub fn wait_for_proof(&self, id: JobId) -> ZkVmResult<ProofReceipt> {
+        let started = Instant::now();
+        loop {
+            match self.job_status(id)? {
+                JobStatus::Succeeded(receipt) => return Ok(receipt),
+                JobStatus::Failed(err) => return Err(ZkVmError::ProofGenerationError(err)),
+                JobStatus::Queued | JobStatus::Running => {}
+            }
+            if self
+                .timeout
+                .is_some_and(|timeout| started.elapsed() >= timeout)
+            {
+                return Err(ZkVmError::ProofGenerationError(format!(
+                    "job {id} did not finish within {:?}",
+                    started.elapsed()
+                )));
+            }
+            thread::sleep(self.poll_interval);
+        }
+    }
+
+    fn program_url(&self, action: &str) -> String {
+        format!(
+            "{}/programs/{}/{action}",
+            self.program.endpoint, self.program.name
+        )
+    }
+}
+
+impl<H: ZkVmHost> ZkVmHost for RemoteHost<H> {
+    type Input<'a> = ZkVmInputRecorder;
+    type ZkVmProofReceipt = RemoteProofReceipt;
+
+    fn prove_inner<'a>(
+        &self,
+        input: RecordedInput,
+        proof_type: ProofType,
+    ) -> ZkVmResult<RemoteProofReceipt> {
+        let id = self.submit(input, proof_type)?;
+        Ok(RemoteProofReceipt(self.wait_for_proof(id)?))
+    }
+
+    fn execute<'a>(&self, input: RecordedInput) -> ZkVmResult<PublicValues> {
+        post(&self.program_url("execute"), &input).map_err(ZkVmError::ExecutionError)
+    }
+
+    fn get_verification_key(&self) -> VerificationKey {
+        self.program.info.verification_key.clone()
+    }
+
+    fn program_id(&self) -> ProgramId {
+        self.program.info.program_id
+    }
+
+    fn get_groth16_verification_key(&self) -> [u8; 32] {
+        self.program.info.groth16_verification_key
+    }
+
+    fn get_elf(&self) -> &[u8] {
+        &self.program.info.elf
+    }
+
+    fn backend_version(&self) -> String {
+        self.program.info.backend_version.clone()
+    }
+
+    fn extract_serde_public_output<T: Serialize + DeserializeOwned>(
+        public_values: &PublicValues,
+    ) -> ZkVmResult<T> {
+        H::extract_serde_public_output(public_values)
+    }
+
+    fn extract_groth16_proof(receipt: &ProofReceipt) -> ZkVmResult<Groth16Proof> {
+        H::extract_groth16_proof(receipt)
+    }
+
+    fn verify_inner(&self, proof: &RemoteProofReceipt) -> ZkVmResult<()> {
+        post(&self.program_url("verify"), &proof.0).map_err(ZkVmError::ProofVerificationError)
+    }
+}
+
+impl<H> fmt::Display for RemoteHost<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "remote_{}@{}", self.program.name, self.program.endpoint)
+    }
+}
+
+/// A [`ProofReceipt`] generated by a prover server.
+#[derive(Debug, Clone)]
+pub struct RemoteProofReceipt(ProofReceipt);
+
+impl TryFrom<ProofReceipt> for RemoteProofReceipt {
+    type Error = ZkVmProofError;
+    fn try_from(value: ProofReceipt) -> Result<Self, Self::Error> {
+        Ok(RemoteProofReceipt(value))
+    }
+}
+
+impl TryFrom<RemoteProofReceipt> for ProofReceipt {
+    type Error = ZkVmProofError;
+    fn try_from(value: RemoteProofReceipt) -> Result<Self, Self::Error> {
+        Ok(value.0)
+    }
+}
+
+fn get<T: BorshDeserialize>(url: &str) -> Result<T, String> {
+    read_response(ureq::get(url).call())
+}
+
+fn post<B: BorshSerialize, T: BorshDeserialize>(url: &str, body: &B) -> Result<T, String> {
+    let body = borsh::to_vec(body).map_err(|e| e.to_string())?;
+    read_response(
+        ureq::post(url)
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(&body),
+    )
+}
+
+fn read_response<T: BorshDeserialize>(
+    res: Result<ureq::Response, ureq::Error>,
+) -> Result<T, String> {
+    // Error statuses still carry the error message in the body
+    let response = match res {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut body)
+        .map_err(|e| e.to_string())?;
+    let res: ApiResult<T> = borsh::from_slice(&body).map_err(|e| e.to_string())?;
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Arc, Mutex};
+
+    use fibonacci::{process_fib, FibProver};
+    use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
+    use strata_zkvm::{ZkVmInputBuilder, ZkVmProver};
+
+    use super::*;
+    use crate::{ProverServer, RunningProverServer};
+
+    fn server() -> ProverServer {
+        let fib_host = NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_fib(zkvm);
+                Ok(())
+            })),
+        };
+        let failing_host = NativeHost {
+            process_proof: Arc::new(Box::new(move |_: &NativeMachine| {
+                Err(ZkVmError::ExecutionError("always fails".to_string()))
+            })),
+        };
+
+        let mut server = ProverServer::new().with_workers(2);
+        server
+            .register("fibonacci", fib_host)
+            .register("failing", failing_host);
+        server
+    }
+
+    fn start_server() -> RunningProverServer {
+        server().start("127.0.0.1:0").unwrap()
+    }
+
+    fn connect(server: &RunningProverServer, name: &str) -> RemoteHost<NativeHost> {
+        RemoteHost::connect(server.endpoint(), name)
+            .unwrap()
+            .with_poll_interval(Duration::from_millis(10))
+    }
+
+    #[test]
+    fn test_remote_prove() {
+        let server = start_server();
+        let host = connect(&server, "fibonacci");
+
+        let receipt = FibProver::prove(&5, &host).unwrap();
+        let output =
+            FibProver::process_output::<RemoteHost<NativeHost>>(receipt.public_values()).unwrap();
+        assert_eq!(output, 5);
+        host.verify(&receipt).unwrap();
+
+        let input = ZkVmInputRecorder::new()
+            .write_serde(&10u32)
+            .unwrap()
+            .build()
+            .unwrap();
+        let public_values = host.execute(input).unwrap();
+        let output: i32 =
+            RemoteHost::<NativeHost>::extract_serde_public_output(&public_values).unwrap();
+        assert_eq!(output, 55);
+    }
+
+    #[test]
+    fn test_remote_errors() {
+        let server = start_server();
+        assert!(matches!(
+            RemoteHost::<NativeHost>::connect(server.endpoint(), "unknown"),
+            Err(ZkVmError::Other(msg)) if msg.contains("unknown program")
+        ));
+
+        let host = connect(&server, "failing");
+        let res = FibProver::prove(&5, &host);
+        assert!(matches!(
+            res,
+            Err(ZkVmError::ProofGenerationError(msg)) if msg.contains("always fails")
+        ));
+        assert!(host.job_status(1234).is_err());
+    }
+
+    #[test]
+    fn test_finished_jobs_expire() {
+        let server = server()
+            .with_job_ttl(Duration::ZERO)
+            .start("127.0.0.1:0")
+            .unwrap();
+        let host = connect(&server, "fibonacci");
+
+        // The first job is dropped when the status of the second one is set
+        FibProver::prove(&5, &host).unwrap();
+        assert!(host.job_status(1).unwrap().is_finished());
+        FibProver::prove(&5, &host).unwrap();
+        assert!(host.job_status(1).is_err());
+        assert!(host.job_status(2).unwrap().is_finished());
+    }
+
+    fn fib_input(n: u32) -> RecordedInput {
+        ZkVmInputRecorder::new()
+            .write_serde(&n)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_large_bodies_are_rejected() {
+        let server = server()
+            .with_max_body_size(64)
+            .start("127.0.0.1:0")
+            .unwrap();
+        let host = connect(&server, "fibonacci");
+
+        assert!(host.submit(fib_input(5), ProofType::Core).is_ok());
+        let input = ZkVmInputRecorder::new()
+            .write_buf(&[0; 1024])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            host.submit(input, ProofType::Core),
+            Err(ZkVmError::ProofGenerationError(msg)) if msg.contains("larger than 64 bytes")
+        ));
+    }
+
+    #[test]
+    fn test_full_queue_is_rejected() {
+        // The host blocks on the gate while the test holds it, and reports each proof it starts
+        let gate = Arc::new(Mutex::new(()));
+        let (started_tx, started_rx) = mpsc::channel();
+        let started_tx = Mutex::new(started_tx);
+        let blocking_gate = gate.clone();
+        let blocking_host = NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                let _ = started_tx.lock().unwrap().send(());
+                drop(blocking_gate.lock().unwrap());
+                process_fib(zkvm);
+                Ok(())
+            })),
+        };
+        let mut server = ProverServer::new().with_max_queued_jobs(1);
+        server.register("blocking", blocking_host);
+        let server = server.start("127.0.0.1:0").unwrap();
+        let host = connect(&server, "blocking");
+
+        // The single worker proves the first job and the second one fills the queue
+        let guard = gate.lock().unwrap();
+        host.submit(fib_input(5), ProofType::Core).unwrap();
+        started_rx.recv().unwrap();
+        let queued = host.submit(fib_input(5), ProofType::Core).unwrap();
+        assert!(matches!(
+            host.submit(fib_input(5), ProofType::Core),
+            Err(ZkVmError::ProofGenerationError(msg)) if msg.contains("job queue is full")
+        ));
+
+        drop(guard);
+        host.wait_for_proof(queued).unwrap();
+    }
+}
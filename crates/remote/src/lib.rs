@@ -0,0 +1,13 @@
+//! Remote proving for zkVM programs.
+//!
+//! [`ProverServer`] hosts registered programs and proves them on behalf of clients, and
+//! [`RemoteHost`] is the [`ZkVmHost`](strata_zkvm::ZkVmHost) used to prove with such a server
+//! while building inputs locally. The `zkvm-prover-server` binary serves SP1 and Risc0 programs
+//! loaded from ELF files.
+
+pub mod api;
+mod client;
+mod server;
+
+pub use client::{RemoteHost, RemoteProofReceipt};
+pub use server::{ProverServer, RunningProverServer};
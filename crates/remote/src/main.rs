@@ -0,0 +1,107 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use argh::FromArgs;
+use strata_zkvm::{ProverBackend, ZkVm};
+use strata_zkvm_remote::ProverServer;
+
+/// Serves zkVM programs to `RemoteHost` clients.
+#[derive(Debug, FromArgs)]
+struct Args {
+    /// address to listen on
+    #[argh(option, default = "String::from(\"127.0.0.1:8080\")")]
+    addr: String,
+
+    /// number of jobs proven concurrently
+    #[argh(option, default = "1")]
+    workers: usize,
+
+    /// programs to serve, as `name=zkvm:path/to/elf` with zkvm `sp1` or `risc0`
+    #[argh(option, short = 'p')]
+    programs: Vec<ProgramArg>,
+
+    /// prover backend: `env`, `local` or `mock`
+    #[argh(option, default = "ProverBackend::Env", from_str_fn(parse_backend))]
+    backend: ProverBackend,
+}
+
+#[derive(Debug)]
+struct ProgramArg {
+    name: String,
+    zkvm: ZkVm,
+    elf: PathBuf,
+}
+
+impl FromStr for ProgramArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid program {s}, expected `name=zkvm:path/to/elf`");
+        let (name, rest) = s.split_once('=').ok_or_else(err)?;
+        let (zkvm, elf) = rest.split_once(':').ok_or_else(err)?;
+        let zkvm = match zkvm {
+            "sp1" => ZkVm::SP1,
+            "risc0" => ZkVm::Risc0,
+            _ => return Err(format!("unsupported zkvm {zkvm}")),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            zkvm,
+            elf: elf.into(),
+        })
+    }
+}
+
+fn parse_backend(s: &str) -> Result<ProverBackend, String> {
+    // Delegating backends make no sense for a server that proves itself
+    match s {
+        "env" => Ok(ProverBackend::Env),
+        "local" => Ok(ProverBackend::Local),
+        "mock" => Ok(ProverBackend::Mock),
+        _ => Err(format!("unsupported backend {s}")),
+    }
+}
+
+fn main() {
+    // Initialize tracing. In order to view logs, run `RUST_LOG=info cargo run`
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
+        .init();
+
+    let args: Args = argh::from_env();
+
+    let mut server = ProverServer::new().with_workers(args.workers);
+    for program in args.programs {
+        let elf = fs::read(&program.elf)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", program.elf.display()));
+        register(&mut server, &program, &elf, args.backend.clone());
+    }
+
+    server
+        .start(args.addr.as_str())
+        .expect("failed to start prover server")
+        .wait();
+}
+
+fn register(server: &mut ProverServer, program: &ProgramArg, elf: &[u8], backend: ProverBackend) {
+    match program.zkvm {
+        #[cfg(feature = "sp1")]
+        ZkVm::SP1 => {
+            let host = strata_sp1_adapter::SP1Host::try_init(elf)
+                .unwrap_or_else(|e| panic!("invalid sp1 program {}: {e}", program.name));
+            server.register(&program.name, host.with_backend(backend));
+        }
+        #[cfg(feature = "risc0")]
+        ZkVm::Risc0 => {
+            let host = strata_risc0_adapter::Risc0Host::try_init(elf)
+                .unwrap_or_else(|e| panic!("invalid risc0 program {}: {e}", program.name));
+            server.register(&program.name, host.with_backend(backend));
+        }
+        zkvm => {
+            let _ = (server, elf, backend);
+            panic!(
+                "cannot serve {}: {zkvm:?} support is not enabled",
+                program.name
+            );
+        }
+    }
+}
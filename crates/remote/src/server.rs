@@ -0,0 +1,400 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    net::ToSocketAddrs,
+    panic,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_zkvm::{DynZkVmHost, ProofReceipt, RecordedInput};
+use tiny_http::{Method, Request, Response, Server};
+use tracing::{info, warn};
+
+use crate::api::{ApiResult, JobId, JobStatus, ProgramInfo, ProveJob};
+
+/// Default number of threads handling HTTP requests.
+const DEFAULT_REQUEST_THREADS: usize = 4;
+
+/// Default time the status of a finished job is kept for clients to fetch it.
+const DEFAULT_JOB_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Default maximum size of a request body, in bytes.
+const DEFAULT_MAX_BODY_SIZE: u64 = 256 << 20;
+
+/// Default number of jobs waiting for a worker.
+const DEFAULT_MAX_QUEUED_JOBS: usize = 64;
+
+/// A server proving registered programs for [`RemoteHost`](crate::RemoteHost) clients.
+///
+/// Programs are registered by name with the host that proves them, which is typically an
+/// `SP1Host` or `Risc0Host` configured with a prover backend, or a `NativeHost` in tests. Prove
+/// requests are queued as jobs and proven by a fixed number of worker threads, while execute and
+/// verify requests are answered directly by a fixed number of request threads. See
+/// [`api`](crate::api) for the routes.
+///
+/// The status of a finished job is dropped once it is older than the job TTL, after which the
+/// job is unknown to the server. Requests with a body larger than the maximum body size are
+/// rejected with status 413, and prove requests are rejected with status 503 while the maximum
+/// number of jobs are waiting for a worker.
+pub struct ProverServer {
+    programs: HashMap<String, Arc<dyn DynZkVmHost>>,
+    workers: usize,
+    request_threads: usize,
+    job_ttl: Duration,
+    max_body_size: u64,
+    max_queued_jobs: usize,
+}
+
+impl Default for ProverServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProverServer {
+    /// Creates a server without programs and with a single worker.
+    pub fn new() -> Self {
+        Self {
+            programs: HashMap::new(),
+            workers: 1,
+            request_threads: DEFAULT_REQUEST_THREADS,
+            job_ttl: DEFAULT_JOB_TTL,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            max_queued_jobs: DEFAULT_MAX_QUEUED_JOBS,
+        }
+    }
+
+    /// Registers `host` under `name`, replacing any program registered with the same name.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        host: impl DynZkVmHost + 'static,
+    ) -> &mut Self {
+        self.programs.insert(name.into(), Arc::new(host));
+        self
+    }
+
+    /// Sets the number of jobs proven concurrently.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Sets the number of HTTP requests handled concurrently.
+    pub fn with_request_threads(mut self, request_threads: usize) -> Self {
+        self.request_threads = request_threads.max(1);
+        self
+    }
+
+    /// Sets how long the status of a finished job is kept.
+    pub fn with_job_ttl(mut self, job_ttl: Duration) -> Self {
+        self.job_ttl = job_ttl;
+        self
+    }
+
+    /// Sets the maximum size of a request body, in bytes.
+    pub fn with_max_body_size(mut self, max_body_size: u64) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Sets the number of jobs that can wait for a worker.
+    pub fn with_max_queued_jobs(mut self, max_queued_jobs: usize) -> Self {
+        self.max_queued_jobs = max_queued_jobs.max(1);
+        self
+    }
+
+    /// Starts serving on `addr` in background threads.
+    pub fn start(self, addr: impl ToSocketAddrs) -> io::Result<RunningProverServer> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        let addr = server
+            .server_addr()
+            .to_ip()
+            .expect("server listens on a tcp socket");
+        let server = Arc::new(server);
+
+        let (queue, jobs_rx) = mpsc::sync_channel(self.max_queued_jobs);
+        let jobs_rx = Arc::new(Mutex::new(jobs_rx));
+        let state = Arc::new(State {
+            programs: self.programs,
+            jobs: Mutex::default(),
+            job_ttl: self.job_ttl,
+            max_body_size: self.max_body_size,
+            next_job_id: AtomicU64::new(1),
+            queue: Mutex::new(Some(queue)),
+        });
+
+        let mut threads: Vec<_> = (0..self.workers)
+            .map(|_| {
+                let state = state.clone();
+                let jobs_rx = jobs_rx.clone();
+                thread::spawn(move || run_worker(&state, &jobs_rx))
+            })
+            .collect();
+        // `Server::incoming_requests` can be iterated from several threads, each request being
+        // received by a single one of them
+        threads.extend((0..self.request_threads).map(|_| {
+            let server = server.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle_request(&state, request);
+                }
+            })
+        }));
+
+        info!(%addr, "prover server started");
+        Ok(RunningProverServer {
+            endpoint: format!("http://{addr}"),
+            server,
+            request_threads: self.request_threads,
+            state,
+            threads,
+        })
+    }
+}
+
+/// A [`ProverServer`] serving in background threads. Dropping it stops the server once the
+/// queued jobs are proven.
+pub struct RunningProverServer {
+    endpoint: String,
+    server: Arc<Server>,
+    request_threads: usize,
+    state: Arc<State>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl RunningProverServer {
+    /// Returns the endpoint clients connect to.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Blocks the current thread while the server is running.
+    pub fn wait(mut self) {
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RunningProverServer {
+    fn drop(&mut self) {
+        // Each unblock stops a single request thread
+        for _ in 0..self.request_threads {
+            self.server.unblock();
+        }
+        // Closing the queue stops the workers once it is drained
+        self.state.queue.lock().expect("queue lock").take();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+struct State {
+    programs: HashMap<String, Arc<dyn DynZkVmHost>>,
+    jobs: Mutex<HashMap<JobId, TrackedJob>>,
+    job_ttl: Duration,
+    max_body_size: u64,
+    next_job_id: AtomicU64,
+    queue: Mutex<Option<mpsc::SyncSender<QueuedJob>>>,
+}
+
+impl State {
+    /// Sets the status of a job, dropping the finished jobs older than the job TTL.
+    fn set_status(&self, id: JobId, status: JobStatus) {
+        let now = Instant::now();
+        let mut jobs = self.jobs.lock().expect("jobs lock");
+        jobs.retain(|_, job| match job.finished_at {
+            Some(finished_at) => now - finished_at < self.job_ttl,
+            None => true,
+        });
+        let finished_at = status.is_finished().then_some(now);
+        jobs.insert(
+            id,
+            TrackedJob {
+                status,
+                finished_at,
+            },
+        );
+    }
+
+    fn program(&self, name: &str) -> Result<&Arc<dyn DynZkVmHost>, (u16, String)> {
+        self.programs
+            .get(name)
+            .ok_or_else(|| (404, format!("unknown program {name}")))
+    }
+}
+
+struct TrackedJob {
+    status: JobStatus,
+    finished_at: Option<Instant>,
+}
+
+struct QueuedJob {
+    id: JobId,
+    host: Arc<dyn DynZkVmHost>,
+    job: ProveJob,
+}
+
+fn run_worker(state: &State, jobs_rx: &Mutex<mpsc::Receiver<QueuedJob>>) {
+    loop {
+        let Ok(QueuedJob { id, host, job }) = jobs_rx.lock().expect("jobs lock").recv() else {
+            return;
+        };
+
+        state.set_status(id, JobStatus::Running);
+        info!(%id, %host, proof_type = ?job.proof_type, "proving job");
+
+        // A panicking prover fails the job instead of taking the worker down
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            host.prove_recorded(&job.input, job.proof_type)
+        }));
+        let status = match res {
+            Ok(Ok(receipt)) => JobStatus::Succeeded(receipt),
+            Ok(Err(err)) => JobStatus::Failed(err.to_string()),
+            Err(_) => JobStatus::Failed("prover panicked".to_string()),
+        };
+        if let JobStatus::Failed(err) = &status {
+            warn!(%id, %err, "proving job failed");
+        }
+        state.set_status(id, status);
+    }
+}
+
+fn handle_request(state: &State, mut request: Request) {
+    let (status, response) = match read_body(&mut request, state.max_body_size) {
+        Ok(body) => route(state, request.method(), request.url(), &body),
+        Err((status, err)) => error_response(status, err),
+    };
+    let _ = request.respond(Response::from_data(response).with_status_code(status));
+}
+
+/// Reads the body of a request, which fails without reading it all if it is larger than
+/// `max_body_size`.
+fn read_body(request: &mut Request, max_body_size: u64) -> Result<Vec<u8>, (u16, String)> {
+    let too_large = || (413, format!("body is larger than {max_body_size} bytes"));
+    if request
+        .body_length()
+        .is_some_and(|len| len as u64 > max_body_size)
+    {
+        return Err(too_large());
+    }
+
+    // Chunked bodies have no length, reading one byte more tells if they are too large
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max_body_size.saturating_add(1))
+        .read_to_end(&mut body)
+        .map_err(|e| (400, format!("invalid body: {e}")))?;
+    if body.len() as u64 > max_body_size {
+        return Err(too_large());
+    }
+    Ok(body)
+}
+
+fn route(state: &State, method: &Method, url: &str, body: &[u8]) -> (u16, Vec<u8>) {
+    let segments: Vec<_> = url.trim_matches('/').split('/').collect();
+    let res = match (method, segments.as_slice()) {
+        (Method::Get, ["programs", name]) => state
+            .program(name)
+            .map(|host| encode(&program_info(&**host))),
+        (Method::Post, ["programs", name, "prove"]) => {
+            submit_job(state, name, body).map(|id| encode(&id))
+        }
+        (Method::Post, ["programs", name, "execute"]) => state.program(name).and_then(|host| {
+            let input: RecordedInput = decode(body)?;
+            host.execute_recorded(&input)
+                .map(|public_values| encode(&public_values))
+                .map_err(|e| (422, e.to_string()))
+        }),
+        (Method::Post, ["programs", name, "verify"]) => state.program(name).and_then(|host| {
+            let receipt: ProofReceipt = decode(body)?;
+            host.verify_receipt(&receipt)
+                .map(|_| encode(&()))
+                .map_err(|e| (422, e.to_string()))
+        }),
+        (Method::Get, ["jobs", id]) => job_status(state, id).map(|status| encode(&status)),
+        _ => Err((404, format!("no route for {method} {url}"))),
+    };
+
+    match res {
+        Ok(body) => (200, body),
+        Err((status, err)) => error_response(status, err),
+    }
+}
+
+fn program_info(host: &dyn DynZkVmHost) -> ProgramInfo {
+    ProgramInfo {
+        elf: host.elf().to_vec(),
+        verification_key: host.verification_key(),
+        program_id: host.guest_program_id(),
+        groth16_verification_key: host.groth16_verification_key(),
+        backend_version: host.zkvm_version(),
+    }
+}
+
+fn submit_job(state: &State, name: &str, body: &[u8]) -> Result<JobId, (u16, String)> {
+    let host = state.program(name)?.clone();
+    let job: ProveJob = decode(body)?;
+
+    let id = state.next_job_id.fetch_add(1, Ordering::Relaxed);
+    state.set_status(id, JobStatus::Queued);
+    let queue = state.queue.lock().expect("queue lock");
+    let res = match queue.as_ref() {
+        Some(queue) => queue.try_send(QueuedJob { id, host, job }),
+        None => Err(mpsc::TrySendError::Disconnected(QueuedJob {
+            id,
+            host,
+            job,
+        })),
+    };
+    if let Err(err) = res {
+        // The id was never returned to the client, so the job is forgotten
+        state.jobs.lock().expect("jobs lock").remove(&id);
+        let err = match err {
+            mpsc::TrySendError::Full(_) => "job queue is full",
+            mpsc::TrySendError::Disconnected(_) => "server is shutting down",
+        };
+        return Err((503, err.to_string()));
+    }
+
+    info!(%id, program = %name, "queued proving job");
+    Ok(id)
+}
+
+fn job_status(state: &State, id: &str) -> Result<JobStatus, (u16, String)> {
+    let id: JobId = id
+        .parse()
+        .map_err(|_| (400, format!("invalid job id {id}")))?;
+    state
+        .jobs
+        .lock()
+        .expect("jobs lock")
+        .get(&id)
+        .map(|job| job.status.clone())
+        .ok_or_else(|| (404, format!("unknown job {id}")))
+}
+
+fn decode<T: BorshDeserialize>(body: &[u8]) -> Result<T, (u16, String)> {
+    borsh::from_slice(body).map_err(|e| (400, format!("invalid body: {e}")))
+}
+
+/// Encodes a successful response.
+fn encode<T: BorshSerialize>(value: &T) -> Vec<u8> {
+    borsh::to_vec(&ApiResult::Ok(value)).expect("serializing to a vec does not fail")
+}
+
+fn error_response(status: u16, err: String) -> (u16, Vec<u8>) {
+    let body =
+        borsh::to_vec(&ApiResult::<()>::Err(err)).expect("serializing to a vec does not fail");
+    (status, body)
+}
@@ -0,0 +1,43 @@
+//! Setup overhead of proving with `Risc0Host` in mock mode.
+//!
+//! Compares proving through the host, which executes the program and wraps its claim in a fake
+//! receipt, against executing the program with a fresh executor.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use risc0_zkvm::default_executor;
+use strata_risc0_adapter::{Risc0Host, Risc0ProofInputBuilder};
+use strata_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF as TEST_ELF;
+use strata_zkvm::{ProofType, ProverBackend, ZkVmHost, ZkVmInputBuilder};
+
+fn bench_host(c: &mut Criterion) {
+    let host = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Mock);
+
+    c.bench_function("risc0_host_clone", |b| b.iter(|| host.clone()));
+
+    c.bench_function("risc0_mock_prove_host", |b| {
+        b.iter(|| {
+            let input = Risc0ProofInputBuilder::new()
+                .write_serde(&1u32)
+                .unwrap()
+                .build()
+                .unwrap();
+            host.prove(input, ProofType::Core).unwrap()
+        })
+    });
+
+    c.bench_function("risc0_execute_fresh_executor", |b| {
+        b.iter(|| {
+            let input = Risc0ProofInputBuilder::new()
+                .write_serde(&1u32)
+                .unwrap()
+                .build()
+                .unwrap();
+            default_executor()
+                .execute(input.into_env(), TEST_ELF)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_host);
+criterion_main!(benches);
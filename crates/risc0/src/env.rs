@@ -1,6 +1,6 @@
-use risc0_zkvm::{guest::env, serde::from_slice};
+use risc0_zkvm::{guest::env, serde::from_slice, sha::Digest};
 use serde::{de::DeserializeOwned, Serialize};
-use strata_zkvm::{Proof, ZkVmEnv};
+use strata_zkvm::{ProgramId, Proof, ZkVmEnv};
 
 use crate::verify_groth16;
 
@@ -15,7 +15,8 @@ impl ZkVmEnv for Risc0ZkVmEnv {
     }
 
     fn read_serde<T: DeserializeOwned>(&self) -> T {
-        env::read()
+        let buf = self.read_buf();
+        bincode::deserialize(&buf).expect("bincode deserialization failed")
     }
 
     fn commit_buf(&self, output_raw: &[u8]) {
@@ -26,9 +27,11 @@ impl ZkVmEnv for Risc0ZkVmEnv {
         env::commit(output);
     }
 
-    fn verify_native_proof(&self, _vk_digest: &[u32; 8], public_values: &[u8]) {
-        let vk: [u32; 8] = env::read();
-        env::verify(vk, public_values).expect("verification failed")
+    fn verify_native_proof(&self, program_id: &ProgramId, public_values: &[u8]) {
+        // The program id supplied by the guest is the image id of the program being verified. It
+        // must never be read from the input, otherwise the host could pick the program.
+        let image_id = Digest::from(*program_id.as_words());
+        env::verify(image_id, public_values).expect("verification failed")
     }
 
     fn verify_groth16_proof(
@@ -40,8 +43,8 @@ impl ZkVmEnv for Risc0ZkVmEnv {
         verify_groth16(proof, verification_key, public_params_raw).unwrap();
     }
 
-    fn read_verified_serde<T: DeserializeOwned>(&self, vk_digest: &[u32; 8]) -> T {
-        let buf = self.read_verified_buf(vk_digest);
+    fn read_verified_serde<T: DeserializeOwned>(&self, program_id: &ProgramId) -> T {
+        let buf = self.read_verified_buf(program_id);
         from_slice(&buf).expect("risc0 zkvm deserialization failed")
     }
 }
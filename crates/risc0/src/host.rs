@@ -1,30 +1,124 @@
-use std::fmt;
+use std::{fmt, rc::Rc, sync::Arc};
 
 use hex::encode;
-use risc0_zkvm::{compute_image_id, default_prover, sha::Digest, Journal, ProverOpts};
+use risc0_zkvm::{
+    compute_image_id, default_executor, default_prover, sha::Digest, ExecutorEnv, FakeReceipt,
+    InnerReceipt, Journal, LocalProver, Prover, ProverOpts, Receipt, ReceiptClaim, VerifierContext,
+};
 use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest as _, Sha256};
 use strata_zkvm::{
-    ProofType, PublicValues, VerificationKey, ZkVmError, ZkVmHost, ZkVmInputBuilder, ZkVmResult,
+    validate_elf, Groth16Proof, ProgramId, Proof, ProofReceipt, ProofType, ProveRequest,
+    ProverBackend, PublicValues, VerificationKey, ZkVm, ZkVmElfError, ZkVmError, ZkVmHost,
+    ZkVmInputBuilder, ZkVmProofError, ZkVmResult,
 };
 
 use crate::{input::Risc0ProofInputBuilder, proof::Risc0ProofReceipt};
 
+/// Maximum size of a guest ELF accepted by [`Risc0Host::try_init`].
+const MAX_ELF_SIZE: usize = 1 << 28;
+
+thread_local! {
+    /// The Risc0 prover is not `Send`, so the prover configured from the environment is created
+    /// lazily once per thread and shared by all the hosts used on that thread.
+    static PROVER: Rc<dyn Prover> = default_prover();
+
+    /// The local prover, shared like [`PROVER`].
+    static LOCAL_PROVER: Rc<dyn Prover> = Rc::new(LocalProver::new("local"));
+}
+
+fn default_backend() -> ProverBackend {
+    if cfg!(feature = "mock") {
+        ProverBackend::Mock
+    } else {
+        ProverBackend::Env
+    }
+}
+
 /// A host for the `Risc0` zkVM that stores the guest program in ELF format
 /// The `Risc0Host` is responsible for program execution and proving
+///
+/// Proofs are generated with the host's [`ProverBackend`], which defaults to
+/// [`ProverBackend::Env`], or to [`ProverBackend::Mock`] with the `mock` feature. Clones of a host
+/// share its ELF.
 #[derive(Clone)]
 pub struct Risc0Host {
-    elf: Vec<u8>,
+    elf: Arc<[u8]>,
     id: Digest,
+    backend: ProverBackend,
 }
 
 impl Risc0Host {
+    /// Initializes a new [`Risc0Host`] from the guest ELF bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ELF is invalid. See [`Risc0Host::try_init`].
     pub fn init(guest_code: &[u8]) -> Self {
-        let id = compute_image_id(guest_code).expect("invalid elf");
-        Risc0Host {
-            elf: guest_code.to_vec(),
+        Self::try_init(guest_code).expect("invalid elf")
+    }
+
+    /// Initializes a new [`Risc0Host`] from the guest ELF bytes.
+    ///
+    /// The ELF is validated with [`validate_elf`] before computing its image id, which is useful
+    /// when the ELF is loaded at runtime.
+    pub fn try_init(guest_code: &[u8]) -> ZkVmResult<Self> {
+        Self::try_init_with_max_size(guest_code, MAX_ELF_SIZE)
+    }
+
+    fn try_init_with_max_size(guest_code: &[u8], max_elf_size: usize) -> ZkVmResult<Self> {
+        validate_elf(guest_code, max_elf_size)?;
+        let id = compute_image_id(guest_code).map_err(|e| ZkVmElfError::Other(e.to_string()))?;
+        let host = Risc0Host {
+            elf: Arc::from(guest_code),
             id,
+            backend: ProverBackend::Env,
+        };
+        Ok(host.with_backend(default_backend()))
+    }
+
+    /// Returns the host with proofs generated by `backend`.
+    ///
+    /// With [`ProverBackend::Mock`] the host generates fake receipts and accepts them when
+    /// verifying, like the Risc0 dev mode, but without enabling the dev mode of the process:
+    /// other hosts keep rejecting fake receipts.
+    pub fn with_backend(mut self, backend: ProverBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Returns the prover backend of the host.
+    pub fn backend(&self) -> &ProverBackend {
+        &self.backend
+    }
+
+    /// Returns the context receipts are verified in.
+    ///
+    /// Only [`ProverBackend::Env`] follows `RISC0_DEV_MODE`, the dev mode is otherwise enabled
+    /// for [`ProverBackend::Mock`] only.
+    fn verifier_context(&self) -> VerifierContext {
+        let ctx = VerifierContext::default();
+        match self.backend {
+            ProverBackend::Env => ctx,
+            ProverBackend::Mock => ctx.with_dev_mode(true),
+            _ => ctx.with_dev_mode(false),
         }
     }
+
+    /// Executes the program and wraps its claim in a fake receipt, which is what the Risc0 dev
+    /// mode proves.
+    fn prove_mock(&self, env: ExecutorEnv<'_>) -> ZkVmResult<Receipt> {
+        let session_info = default_executor()
+            .execute(env, &self.elf)
+            .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?;
+
+        let journal = session_info.journal.bytes;
+        let claim = ReceiptClaim::ok(self.id, journal.clone());
+        Ok(Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(claim)),
+            journal,
+        ))
+    }
 }
 
 impl ZkVmHost for Risc0Host {
@@ -36,9 +130,20 @@ impl ZkVmHost for Risc0Host {
         prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
         proof_type: ProofType,
     ) -> ZkVmResult<Risc0ProofReceipt> {
-        #[cfg(feature = "mock")]
-        {
-            std::env::set_var("RISC0_DEV_MODE", "true");
+        if self.backend.is_delegated() {
+            let request = ProveRequest {
+                zkvm: ZkVm::Risc0,
+                elf: self.elf.to_vec(),
+                input: prover_input.recorded,
+                proof_type,
+            };
+            let receipt = self.backend.prove_delegated(&request)?;
+            return Ok(Risc0ProofReceipt::try_from(&receipt)?);
+        }
+
+        let env = prover_input.env;
+        if self.backend == ProverBackend::Mock {
+            return Ok(self.prove_mock(env)?.into());
         }
 
         // Setup the prover
@@ -48,16 +153,31 @@ impl ZkVmHost for Risc0Host {
             ProofType::Groth16 => ProverOpts::groth16(),
         };
 
-        let prover = default_prover();
-
-        // Generate the proof
+        // Generate the proof. The local prover never runs in dev mode, whatever the environment
+        let (prover, opts) = match &self.backend {
+            ProverBackend::Local => (&LOCAL_PROVER, opts.with_dev_mode(false)),
+            _ => (&PROVER, opts),
+        };
         let proof_info = prover
-            .prove_with_opts(prover_input, &self.elf, &opts)
+            .with(|prover| prover.prove_with_opts(env, &self.elf, &opts))
             .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?;
 
         Ok(proof_info.receipt.into())
     }
 
+    fn execute<'a>(
+        &self,
+        prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+    ) -> ZkVmResult<PublicValues> {
+        let executor = default_executor();
+
+        let session_info = executor
+            .execute(prover_input.env, &self.elf)
+            .map_err(|e| ZkVmError::ExecutionError(e.to_string()))?;
+
+        Ok(PublicValues::new(session_info.journal.bytes))
+    }
+
     fn extract_serde_public_output<T: Serialize + DeserializeOwned>(
         proof: &PublicValues,
     ) -> ZkVmResult<T> {
@@ -69,18 +189,44 @@ impl ZkVmHost for Risc0Host {
             })
     }
 
+    fn extract_groth16_proof(receipt: &ProofReceipt) -> ZkVmResult<Groth16Proof> {
+        let receipt = Risc0ProofReceipt::try_from(receipt)?;
+        let groth16_receipt = receipt
+            .as_ref()
+            .inner
+            .groth16()
+            .map_err(|_| ZkVmProofError::InvalidProofType(ProofType::Groth16))?;
+
+        let seal = Proof::new(groth16_receipt.seal.clone());
+        let public_values_digest = Sha256::digest(&receipt.as_ref().journal.bytes).into();
+        Ok(Groth16Proof::new(seal, public_values_digest))
+    }
+
     fn get_elf(&self) -> &[u8] {
         &self.elf
     }
 
+    fn backend_version(&self) -> String {
+        format!("risc0-{}", risc0_zkvm::VERSION)
+    }
+
     fn get_verification_key(&self) -> VerificationKey {
         VerificationKey::new(self.id.as_bytes().to_vec())
     }
 
+    fn program_id(&self) -> ProgramId {
+        ProgramId::new(self.id.into())
+    }
+
+    fn get_groth16_verification_key(&self) -> [u8; 32] {
+        // Risc0 Groth16 proofs commit to the image id of the program
+        self.id.into()
+    }
+
     fn verify_inner(&self, proof: &Risc0ProofReceipt) -> ZkVmResult<()> {
         proof
             .as_ref()
-            .verify(self.id)
+            .verify_with_context(&self.verifier_context(), self.id)
             .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))?;
         Ok(())
     }
@@ -92,105 +238,246 @@ impl fmt::Display for Risc0Host {
     }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use std::{fs::File, io::Write};
-
-//     use super::*;
-//     use crate::Risc0ProofInputBuilder;
-
-//     // Adding compiled guest code `TEST_ELF` to save the build time
-//     // use risc0_zkvm::guest::env;
-//     // fn main() {
-//     //     let input: u32 = env::read();
-//     //     env::commit(&input);
-//     // }
-//     const TEST_ELF: &[u8] = include_bytes!("../tests/elf/risc0-zkvm-elf");
-
-//     #[test]
-//     fn test_mock_prover() {
-//         let input: u32 = 1;
-//         let host = Risc0Host::init(TEST_ELF);
-
-//         // prepare input
-//         let mut zkvm_input_builder = Risc0ProofInputBuilder::new();
-//         zkvm_input_builder.write_serde(&input).unwrap();
-//         let zkvm_input = zkvm_input_builder.build().unwrap();
-
-//         // assert proof generation works
-//         let proof = host
-//             .prove(zkvm_input, ProofType::Core)
-//             .expect("Failed to generate proof");
-
-//         // assert proof verification works
-//         host.verify(&proof).expect("Proof verification failed");
-
-//         // assert public outputs extraction from proof  works
-//         let out: u32 = Risc0Host::extract_serde_public_output(proof.public_values())
-//             .expect("Failed to extract public outputs");
-//         assert_eq!(input, out)
-//     }
-
-//     #[test]
-//     fn test_mock_prover_with_public_param() {
-//         let input: u32 = 1;
-//         let zkvm = Risc0Host::init(TEST_ELF);
-
-//         // prepare input
-//         let mut zkvm_input_builder = Risc0ProofInputBuilder::new();
-//         zkvm_input_builder.write_serde(&input).unwrap();
-//         let zkvm_input = zkvm_input_builder.build().unwrap();
-
-//         // assert proof generation works
-//         let proof = zkvm
-//             .prove(zkvm_input, ProofType::Core)
-//             .expect("Failed to generate proof");
-
-//         // assert proof verification works
-//         zkvm.verify(&proof).expect("Proof verification failed");
-
-//         // assert public outputs extraction from proof  works
-//         let out: u32 = Risc0Host::extract_serde_public_output(proof.public_values()).expect(
-//             "Failed to extract public
-// outputs",
-//         );
-//         assert_eq!(input, out)
-//     }
-
-//     #[test]
-//     #[ignore]
-//     fn test_groth16_proof_gen() {
-//         // Initialize tracing. In order to view logs, run `RUST_LOG=info cargo run`
-//         tracing_subscriber::fmt()
-//             .with_env_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
-//             .init();
-
-//         let input: u32 = 1;
-
-//         let zkvm = Risc0Host::init(TEST_ELF);
-
-//         // prepare input
-//         let zkvm_input = Risc0ProofInputBuilder::new()
-//             .write_serde(&input)
-//             .unwrap()
-//             .build()
-//             .unwrap();
-
-//         // assert proof generation works
-//         let proof = zkvm
-//             .prove(zkvm_input, ProofType::Groth16)
-//             .expect("Failed to generate proof");
-
-//         let expected_vk = vec![
-//             48, 77, 52, 1, 100, 95, 109, 135, 223, 56, 83, 146, 244, 21, 237, 63, 198, 105, 2,
-// 75,             135, 48, 52, 165, 178, 24, 200, 186, 174, 191, 212, 184,
-//         ];
-
-//         let filename = "proof-groth16.bin";
-//         let mut file = File::create(filename).unwrap();
-//         file.write_all(&bincode::serialize(&proof).expect("bincode serialization failed"))
-//             .unwrap();
-
-//         assert_eq!(zkvm.get_verification_key().as_bytes(), expected_vk);
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use fibonacci::process_fib;
+    use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
+    use strata_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF;
+    use strata_zkvm::{
+        DifferentialHarness, ExecutionOutcome, ProofFixtures, ZkVmInputResult, ZkVmProver,
+    };
+
+    use super::*;
+    use crate::Risc0ProverInput;
+
+    // The fibonacci guest, built from source by the artifacts crate so that it always matches the
+    // input format of `Risc0ProofInputBuilder`
+    const TEST_ELF: &[u8] = GUEST_RISC0_FIBONACCI_ELF;
+
+    /// The golden proofs of `TEST_ELF` with input `1`, regenerated with `UPDATE_FIXTURES=1`.
+    fn fixtures() -> ProofFixtures {
+        ProofFixtures::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+    }
+
+    fn prover_input(input: u32) -> Risc0ProverInput<'static> {
+        Risc0ProofInputBuilder::new()
+            .write_serde(&input)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_try_init_rejects_invalid_elf() {
+        let res = Risc0Host::try_init(b"not an elf");
+        assert!(matches!(
+            res,
+            Err(ZkVmError::InvalidELF(ZkVmElfError::NotElf))
+        ));
+
+        let res = Risc0Host::try_init_with_max_size(TEST_ELF, TEST_ELF.len() - 1);
+        assert!(matches!(
+            res,
+            Err(ZkVmError::InvalidELF(ZkVmElfError::TooLarge { .. }))
+        ));
+        assert!(Risc0Host::try_init_with_max_size(TEST_ELF, TEST_ELF.len()).is_ok());
+    }
+
+    #[test]
+    fn test_mock_prover() {
+        let input: u32 = 1;
+        let host = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Mock);
+
+        // assert proof generation works
+        let proof = host
+            .prove(prover_input(input), ProofType::Core)
+            .expect("Failed to generate proof");
+
+        // assert proof verification works
+        host.verify(&proof).expect("Proof verification failed");
+
+        // assert public outputs extraction from proof  works
+        let out: i32 = Risc0Host::extract_serde_public_output(proof.public_values())
+            .expect("Failed to extract public outputs");
+        assert_eq!(out, 1)
+    }
+
+    #[test]
+    fn test_mock_backend_is_local_to_the_host() {
+        let mock = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Mock);
+        let receipt = mock.prove(prover_input(1), ProofType::Core).unwrap();
+        mock.verify(&receipt).unwrap();
+        assert!(std::env::var("RISC0_DEV_MODE").is_err());
+
+        // Hosts on the other backends still reject the fake receipt
+        for backend in [ProverBackend::Env, ProverBackend::Local] {
+            let host = Risc0Host::init(TEST_ELF).with_backend(backend);
+            assert!(matches!(
+                host.verify(&receipt),
+                Err(ZkVmError::ProofVerificationError(_))
+            ));
+        }
+    }
+
+    /// Proves the `TEST_ELF` program, which commits the fibonacci number of its input.
+    struct FibProver;
+
+    impl ZkVmProver for FibProver {
+        type Input = u32;
+        type Output = i32;
+
+        fn proof_type() -> ProofType {
+            ProofType::Core
+        }
+
+        fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+        where
+            B: ZkVmInputBuilder<'a>,
+        {
+            B::new().write_serde(input)?.build()
+        }
+
+        fn process_output<H>(public_values: &PublicValues) -> ZkVmResult<Self::Output>
+        where
+            H: ZkVmHost,
+        {
+            H::extract_serde_public_output(public_values)
+        }
+    }
+
+    #[test]
+    fn test_mock_prover_batch() {
+        // Every worker thread proves with its own thread-local prover
+        let host = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Mock);
+        let inputs = [1, 2, 3, 4, 5];
+        let results = FibProver::prove_batch(&inputs, &host, 2);
+        let outputs: Vec<_> = results
+            .into_iter()
+            .map(|res| {
+                let receipt = res.unwrap();
+                host.verify(&receipt).unwrap();
+                FibProver::process_output::<Risc0Host>(receipt.public_values()).unwrap()
+            })
+            .collect();
+        assert_eq!(outputs, [1, 1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn test_mock_differential() {
+        // The native host commits bincode, the guest the serde of Risc0, which the harness
+        // compares once decoded by each host
+        let native = NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_fib(zkvm);
+                Ok(())
+            })),
+        };
+        let harness = DifferentialHarness::<FibProver>::new(native)
+            .host(Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Mock));
+        for input in [0, 1, 10, 100] {
+            let outcomes = harness.check(&input).unwrap();
+            assert_eq!(outcomes.len(), 2);
+            assert!(matches!(outcomes[1], ExecutionOutcome::Committed(_)));
+        }
+    }
+
+    /// Generates a Groth16 proof of `TEST_ELF` with the local prover, which needs Docker.
+    fn prove_groth16(host: &Risc0Host) -> ProofReceipt {
+        host.prove(prover_input(1), ProofType::Groth16)
+            .expect("Failed to generate proof")
+    }
+
+    #[test]
+    #[ignore = "generates a Groth16 proof, which needs Docker"]
+    fn test_groth16_proof_extraction() {
+        let host = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Local);
+        let receipt = prove_groth16(&host);
+
+        // assert the extracted seal verifies with the crate's own verifier
+        let groth16_proof = Risc0Host::extract_groth16_proof(&receipt).unwrap();
+        assert_eq!(
+            groth16_proof.public_values_digest(),
+            &<[u8; 32]>::from(Sha256::digest(receipt.public_values().as_bytes()))
+        );
+        crate::verify_groth16(
+            groth16_proof.seal(),
+            &host.get_groth16_verification_key(),
+            receipt.public_values().as_bytes(),
+        )
+        .expect("Groth16 proof verification failed");
+
+        // assert the seal does not verify against other public values
+        let mut public_values = receipt.public_values().as_bytes().to_vec();
+        public_values[0] ^= 1;
+        assert!(crate::verify_groth16(
+            groth16_proof.seal(),
+            &host.get_groth16_verification_key(),
+            &public_values,
+        )
+        .is_err());
+
+        // assert non-Groth16 receipts are rejected
+        let host = host.with_backend(ProverBackend::Mock);
+        let receipt = host.prove(prover_input(1), ProofType::Core).unwrap();
+        assert!(matches!(
+            Risc0Host::extract_groth16_proof(&receipt),
+            Err(ZkVmError::InvalidProofReceipt(
+                ZkVmProofError::InvalidProofType(ProofType::Groth16)
+            ))
+        ));
+    }
+
+    #[test]
+    #[ignore = "generates a Groth16 proof, which needs Docker"]
+    fn test_groth16_canonical_roundtrip() {
+        let host = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Local);
+        let receipt = prove_groth16(&host);
+        let groth16_proof = Risc0Host::extract_groth16_proof(&receipt).unwrap();
+        let verification_key = host.get_groth16_verification_key();
+
+        // assert the canonical encodings round-trip to a seal accepted by the real verifier
+        let canonical =
+            crate::to_canonical_groth16_proof(&groth16_proof, &verification_key).unwrap();
+        let calldata = canonical.to_solidity_calldata();
+        let decoded =
+            strata_zkvm::CanonicalGroth16Proof::from_solidity_calldata(&calldata).unwrap();
+        assert_eq!(decoded, canonical);
+
+        let seal = crate::from_canonical_groth16_proof(&decoded);
+        assert_eq!(&seal, groth16_proof.seal());
+        crate::verify_groth16(&seal, &verification_key, receipt.public_values().as_bytes())
+            .expect("Groth16 proof verification failed");
+    }
+
+    #[test]
+    #[ignore = "the fixtures in tests/fixtures are not generated yet, see the README"]
+    fn test_golden_fixtures() {
+        let input: u32 = 1;
+        let fixtures = fixtures();
+        let host = Risc0Host::init(TEST_ELF).with_backend(ProverBackend::Local);
+
+        // Fails whenever the ELF or the Risc0 version changes the keys
+        let snapshot = fixtures.check_vk_snapshot(&host).unwrap();
+
+        for proof_type in [ProofType::Core, ProofType::Compressed, ProofType::Groth16] {
+            let receipt = fixtures
+                .receipt(proof_type, || host.prove(prover_input(input), proof_type))
+                .unwrap();
+
+            host.verify(&receipt)
+                .unwrap_or_else(|e| panic!("golden {proof_type:?} proof: {e}"));
+            let out: i32 = Risc0Host::extract_serde_public_output(receipt.public_values()).unwrap();
+            assert_eq!(out, 1);
+        }
+
+        // The golden Groth16 proof verifies with the crate's own verifier
+        let receipt = fixtures.load_receipt(ProofType::Groth16).unwrap();
+        let groth16_proof = Risc0Host::extract_groth16_proof(&receipt).unwrap();
+        crate::verify_groth16(
+            groth16_proof.seal(),
+            &snapshot.groth16_verification_key,
+            receipt.public_values().as_bytes(),
+        )
+        .expect("golden Groth16 proof verification failed");
+    }
+}
@@ -1,41 +1,95 @@
-use risc0_zkvm::{sha::Digest, ExecutorEnv, ExecutorEnvBuilder};
+use risc0_zkvm::{sha::Digest, ExecutorEnv, ExecutorEnvBuilder, VerifierContext};
 use strata_zkvm::{
-    AggregationInput, DataFormatError, ZkVmInputBuilder, ZkVmInputError, ZkVmInputResult,
-    ZkVmVerificationKeyError,
+    AggregationInput, DataFormatError, RecordedInput, ZkVmInputBuilder, ZkVmInputError,
+    ZkVmInputRecorder, ZkVmInputResult,
 };
 
 use crate::proof::Risc0ProofReceipt;
 
-pub struct Risc0ProofInputBuilder<'a>(ExecutorEnvBuilder<'a>);
+/// The input of a Risc0 program.
+///
+/// Besides the [`ExecutorEnv`] proven locally, it keeps the items it was built from as a
+/// [`RecordedInput`], which is what delegated prover backends are sent.
+pub struct Risc0ProverInput<'a> {
+    pub(crate) env: ExecutorEnv<'a>,
+    pub(crate) stdin: Vec<u8>,
+    pub(crate) recorded: RecordedInput,
+}
+
+impl<'a> Risc0ProverInput<'a> {
+    /// Returns the items the input was built from.
+    pub fn recorded(&self) -> &RecordedInput {
+        &self.recorded
+    }
+
+    /// Returns the bytes written to the standard input of the guest, i.e. the frames of the
+    /// input.
+    pub fn stdin(&self) -> &[u8] {
+        &self.stdin
+    }
+
+    /// Consumes the input and returns the environment passed to the Risc0 executor and prover.
+    pub fn into_env(self) -> ExecutorEnv<'a> {
+        self.env
+    }
+}
+
+pub struct Risc0ProofInputBuilder<'a> {
+    env: ExecutorEnvBuilder<'a>,
+    stdin: Vec<u8>,
+    recorder: ZkVmInputRecorder,
+}
+
+impl Risc0ProofInputBuilder<'_> {
+    /// Appends a length-prefixed frame to the standard input of the guest only.
+    ///
+    /// The length is a little-endian `u32`, which is how `ExecutorEnvBuilder::write` encodes it
+    /// and what `Risc0ZkVmEnv::read_buf` reads back with `env::read`.
+    // TODO: replace this with `write_frame` once the API stabilizies
+    fn write_frame(&mut self, item: &[u8]) -> ZkVmInputResult<()> {
+        let len = u32::try_from(item.len()).map_err(|_| {
+            ZkVmInputError::DataFormat(DataFormatError::Other(format!(
+                "input frame of {} bytes is too large",
+                item.len()
+            )))
+        })?;
+        self.stdin.extend_from_slice(&len.to_le_bytes());
+        self.stdin.extend_from_slice(item);
+        Ok(())
+    }
+}
 
 impl<'a> ZkVmInputBuilder<'a> for Risc0ProofInputBuilder<'a> {
-    type Input = ExecutorEnv<'a>;
+    type Input = Risc0ProverInput<'a>;
     type ZkVmProofReceipt = Risc0ProofReceipt;
 
     fn new() -> Self {
-        let env_builder = ExecutorEnv::builder();
-        Self(env_builder)
+        Self {
+            env: ExecutorEnv::builder(),
+            stdin: Vec::new(),
+            recorder: ZkVmInputRecorder::new(),
+        }
     }
 
     fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
-        self.0
-            .write(item)
-            .map_err(|e| ZkVmInputError::DataFormat(DataFormatError::Serde(e.to_string())))?;
+        // Serde inputs are bincode-encoded like on the other backends, so that an input recorded
+        // with `ZkVmInputRecorder` can be replayed here as a plain buffer.
+        let slice = bincode::serialize(item)?;
+        self.write_frame(&slice)?;
+        self.recorder.write_serde(item)?;
         Ok(self)
     }
 
     fn write_borsh<T: borsh::BorshSerialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
         let slice = borsh::to_vec(item)?;
-        self.write_buf(&slice)
+        self.write_frame(&slice)?;
+        self.recorder.write_borsh(item)?;
+        Ok(self)
     }
 
-    // TODO: replace this with `write_frame` once the API stabilizies
     fn write_buf(&mut self, item: &[u8]) -> ZkVmInputResult<&mut Self> {
-        let len = item.len() as u32;
-        self.0
-            .write(&len)
-            .map_err(|e| ZkVmInputError::DataFormat(DataFormatError::Serde(e.to_string())))?;
-        self.0.write_slice(item);
+        self.write_frame(item)?;
+        self.recorder.write_buf(item)?;
         Ok(self)
     }
 
@@ -45,29 +99,138 @@ impl<'a> ZkVmInputBuilder<'a> for Risc0ProofInputBuilder<'a> {
             .receipt()
             .try_into()
             .map_err(ZkVmInputError::ProofReceipt)?;
-        let vk: Digest = item
-            .vk()
-            .as_bytes()
-            .try_into()
-            .map_err(|_| ZkVmVerificationKeyError::InvalidVerificationKeySize)
-            .map_err(ZkVmInputError::VerificationKey)?;
+        let image_id = Digest::from(*item.program_id().as_words());
+
+        // The guest verifies the assumption against the image id it expects, so a receipt for any
+        // other program would only surface as a failure deep inside proving. Check it upfront.
+        // Fake receipts of mock hosts are accepted here, whether they can be used as an
+        // assumption is up to the prover of the guest.
+        receipt
+            .as_ref()
+            .verify_with_context(&VerifierContext::default().with_dev_mode(true), image_id)
+            .map_err(|e| ZkVmInputError::ProofVerification(e.to_string()))?;
 
-        // Write the verification key of the program that'll be proven in the guest.
-        // Note: The vkey is written here so we don't have to hardcode it in guest code.
-        // TODO: This should be fixed once the guest code is finalized
-        self.write_buf(&receipt.as_ref().journal.bytes)?;
-        self.0
-            .write(&vk)
-            .map_err(|e| ZkVmInputError::DataFormat(DataFormatError::Serde(e.to_string())))?;
+        // Write the public values of the program that'll be proven inside zkVM. The image id is
+        // not written since the guest verifies against the digest it is given.
+        self.write_frame(&receipt.as_ref().journal.bytes)?;
 
-        self.0.add_assumption(receipt.inner());
+        self.env.add_assumption(receipt.inner());
 
+        self.recorder.write_proof(item)?;
         Ok(self)
     }
 
     fn build(&mut self) -> ZkVmInputResult<Self::Input> {
-        self.0
+        let stdin = self.stdin.clone();
+        let env = self
+            .env
+            .write_slice(&stdin)
             .build()
-            .map_err(|e| ZkVmInputError::InputBuild(e.to_string()))
+            .map_err(|e| ZkVmInputError::InputBuild(e.to_string()))?;
+        Ok(Risc0ProverInput {
+            env,
+            stdin,
+            recorded: self.recorder.build()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF;
+    use strata_zkvm::{
+        ProgramId, ProofReceipt, ProofType, ProverBackend, RecordedInputItem, VerificationKey,
+        ZkVmHost,
+    };
+
+    use super::*;
+    use crate::Risc0Host;
+
+    const OTHER_PROGRAM_ID: ProgramId = ProgramId::new([8, 7, 6, 5, 4, 3, 2, 1]);
+
+    /// Proves the fibonacci guest with the mock backend, which generates a fake receipt, and
+    /// returns it along with the program id of the guest.
+    fn fake_receipt(input: u32) -> (ProofReceipt, ProgramId) {
+        let host = Risc0Host::init(GUEST_RISC0_FIBONACCI_ELF).with_backend(ProverBackend::Mock);
+        let input = Risc0ProofInputBuilder::new()
+            .write_serde(&input)
+            .unwrap()
+            .build()
+            .unwrap();
+        let receipt = host.prove(input, ProofType::Core).unwrap();
+        (receipt, host.program_id())
+    }
+
+    fn aggregation_input(receipt: ProofReceipt, program_id: ProgramId) -> AggregationInput {
+        AggregationInput::new(
+            receipt,
+            program_id,
+            VerificationKey::new(program_id.to_bytes().to_vec()),
+        )
+    }
+
+    #[test]
+    fn test_write_proof_accepts_matching_program() {
+        let (receipt, program_id) = fake_receipt(10);
+        let item = aggregation_input(receipt, program_id);
+
+        let mut builder = Risc0ProofInputBuilder::new();
+        assert!(builder.write_proof(&item).is_ok());
+
+        // The proof is recorded as is, so that delegated backends can add the same assumption
+        let input = builder.build().unwrap();
+        assert!(matches!(
+            input.recorded().items(),
+            [RecordedInputItem::Proof(recorded)] if recorded == &item
+        ));
+    }
+
+    #[test]
+    fn test_write_proof_rejects_wrong_program() {
+        let (receipt, _) = fake_receipt(10);
+        let item = aggregation_input(receipt, OTHER_PROGRAM_ID);
+
+        let mut builder = Risc0ProofInputBuilder::new();
+        let res = builder.write_proof(&item);
+        assert!(matches!(res, Err(ZkVmInputError::ProofVerification(_))));
+    }
+
+    /// Splits the standard input of the guest into the frames read by `Risc0ZkVmEnv::read_buf`.
+    fn frames(mut stdin: &[u8]) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        while !stdin.is_empty() {
+            let (len, rest) = stdin.split_at(4);
+            let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+            frames.push(rest[..len].to_vec());
+            stdin = &rest[len..];
+        }
+        frames
+    }
+
+    #[test]
+    fn test_write_proof_frame_layout() {
+        let (receipt, program_id) = fake_receipt(10);
+        let public_values = receipt.public_values().as_bytes().to_vec();
+        let item = aggregation_input(receipt, program_id);
+
+        let input = Risc0ProofInputBuilder::new()
+            .write_serde(&7u32)
+            .unwrap()
+            .write_proof(&item)
+            .unwrap()
+            .write_buf(&[9, 9])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // A proof input contributes exactly one frame holding its public values, the proof itself
+        // is passed as an assumption.
+        let expected = vec![
+            bincode::serialize(&7u32).unwrap(),
+            public_values,
+            vec![9, 9],
+        ];
+        assert_eq!(frames(input.stdin()), expected);
+        assert_eq!(input.recorded().items().len(), 3);
     }
 }
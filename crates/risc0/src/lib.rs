@@ -1,3 +1,7 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[cfg(feature = "prover")]
 mod host;
 #[cfg(feature = "prover")]
@@ -5,14 +9,18 @@ mod input;
 #[cfg(feature = "prover")]
 pub use host::Risc0Host;
 #[cfg(feature = "prover")]
-pub use input::Risc0ProofInputBuilder;
+pub use input::{Risc0ProofInputBuilder, Risc0ProverInput};
 #[cfg(feature = "prover")]
 mod proof;
+#[cfg(feature = "prover")]
+pub use proof::Risc0ProofReceipt;
 
 mod verifier;
 pub use verifier::*;
 
+#[cfg(feature = "std")]
 mod env;
+#[cfg(feature = "std")]
 pub use env::Risc0ZkVmEnv;
 
 #[cfg(feature = "perf")]
@@ -15,7 +15,7 @@ impl ZkVmHostPerf for Risc0Host {
         std::env::set_var("RISC0_PPROF_OUT", format!("{}.pb", report_name));
 
         // TODO: handle error
-        let session_info = executor.execute(input, self.get_elf()).unwrap();
+        let session_info = executor.execute(input.into_env(), self.get_elf()).unwrap();
 
         Ok(ProofReport {
             cycles: session_info.cycles(),
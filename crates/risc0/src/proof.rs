@@ -1,5 +1,5 @@
 use risc0_zkvm::{InnerReceipt, Receipt};
-use strata_zkvm::{Proof, ProofReceipt, PublicValues, ZkVmProofError};
+use strata_zkvm::{deserialize_proof, Proof, ProofReceipt, PublicValues, ZkVmProofError};
 
 #[derive(Debug, Clone)]
 pub struct Risc0ProofReceipt(Receipt);
@@ -33,8 +33,7 @@ impl TryFrom<&ProofReceipt> for Risc0ProofReceipt {
     type Error = ZkVmProofError;
     fn try_from(value: &ProofReceipt) -> Result<Self, Self::Error> {
         let journal = value.public_values().as_bytes().to_vec();
-        let inner: InnerReceipt = bincode::deserialize(value.proof().as_bytes())
-            .map_err(|e| ZkVmProofError::DataFormat(e.into()))?;
+        let inner: InnerReceipt = deserialize_proof(value.proof().as_bytes())?;
         Ok(Receipt::new(inner, journal).into())
     }
 }
@@ -1,6 +1,28 @@
-use risc0_zkvm::{Groth16Receipt, MaybePruned, ReceiptClaim};
+use alloc::{string::ToString, vec, vec::Vec};
+
+use risc0_zkvm::{
+    sha::{self, Digestible},
+    Groth16Receipt, Groth16ReceiptVerifierParameters, MaybePruned, ReceiptClaim,
+};
 use sha2::Digest;
-use strata_zkvm::{Proof, ZkVmResult};
+#[cfg(feature = "bn254")]
+use strata_zkvm::GROTH16_COMPACT_SIZE;
+use strata_zkvm::{CanonicalGroth16Proof, Groth16Proof, Groth16Verifier, Proof, ZkVmResult};
+
+/// A [`Groth16Verifier`] for Groth16 proofs generated by Risc0.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Risc0Groth16Verifier;
+
+impl Groth16Verifier for Risc0Groth16Verifier {
+    fn verify_groth16(
+        &self,
+        proof: &Proof,
+        verification_key: &[u8; 32],
+        public_values: &[u8],
+    ) -> ZkVmResult<()> {
+        verify_groth16(proof, verification_key, public_values)
+    }
+}
 
 pub fn verify_groth16(
     proof: &Proof,
@@ -29,6 +51,103 @@ pub fn verify_groth16(
         .map_err(|e| strata_zkvm::ZkVmError::ProofVerificationError(e.to_string()))
 }
 
+/// Converts a [`Groth16Proof`] extracted from a Risc0 receipt into a [`CanonicalGroth16Proof`].
+///
+/// The public inputs of the Risc0 Groth16 circuit are the control root and the digest of the
+/// receipt claim, each split into two 128-bit halves, followed by the BN254 control id.
+pub fn to_canonical_groth16_proof(
+    proof: &Groth16Proof,
+    verification_key: &[u8; 32],
+) -> ZkVmResult<CanonicalGroth16Proof> {
+    let params = Groth16ReceiptVerifierParameters::default();
+
+    let claim = ReceiptClaim::ok(
+        sha::Digest::from_bytes(*verification_key),
+        MaybePruned::<Vec<u8>>::Pruned(sha::Digest::from_bytes(*proof.public_values_digest())),
+    );
+
+    let (control_root_0, control_root_1) = split_digest(params.control_root);
+    let (claim_0, claim_1) = split_digest(claim.digest::<sha::Impl>());
+    let mut bn254_control_id = digest_to_bytes(&params.bn254_control_id);
+    bn254_control_id.reverse();
+
+    CanonicalGroth16Proof::from_gnark_bytes(
+        proof.seal().as_bytes(),
+        vec![
+            control_root_0,
+            control_root_1,
+            claim_0,
+            claim_1,
+            bn254_control_id,
+        ],
+    )
+}
+
+/// Converts a [`CanonicalGroth16Proof`] back into the seal expected by [`verify_groth16`].
+pub fn from_canonical_groth16_proof(proof: &CanonicalGroth16Proof) -> Proof {
+    Proof::new(proof.to_gnark_bytes().to_vec())
+}
+
+/// Converts a [`Groth16Proof`] extracted from a Risc0 receipt into the compact encoding of
+/// [`CanonicalGroth16Proof::to_compact_bytes`].
+#[cfg(feature = "bn254")]
+pub fn to_compact_groth16_proof(
+    proof: &Groth16Proof,
+    verification_key: &[u8; 32],
+) -> ZkVmResult<[u8; GROTH16_COMPACT_SIZE]> {
+    to_canonical_groth16_proof(proof, verification_key)?.to_compact_bytes()
+}
+
+/// Converts a compact proof back into the seal expected by [`verify_groth16`].
+#[cfg(feature = "bn254")]
+pub fn from_compact_groth16_proof(bytes: &[u8]) -> ZkVmResult<Proof> {
+    let proof = CanonicalGroth16Proof::from_compact_bytes(bytes, Vec::new())?;
+    Ok(from_canonical_groth16_proof(&proof))
+}
+
+/// Splits a digest into two big-endian scalars holding its lower and upper 128 bits, as done by
+/// the Risc0 Groth16 verifier.
+fn split_digest(digest: sha::Digest) -> ([u8; 32], [u8; 32]) {
+    let mut big_endian = digest_to_bytes(&digest);
+    big_endian.reverse();
+
+    let mut lower = [0u8; 32];
+    let mut upper = [0u8; 32];
+    lower[16..].copy_from_slice(&big_endian[16..]);
+    upper[16..].copy_from_slice(&big_endian[..16]);
+    (lower, upper)
+}
+
+fn digest_to_bytes(digest: &sha::Digest) -> [u8; 32] {
+    digest.as_bytes().try_into().expect("digest is 32 bytes")
+}
+
+#[cfg(all(test, feature = "bn254"))]
+mod tests {
+    use strata_zkvm::GROTH16_POINTS_SIZE;
+
+    use super::*;
+
+    /// The compact encoding of a proof made of the generators of BN254.
+    const COMPACT_PROOF: &str = "8000000000000000000000000000000000000000000000000000000000000001998e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6edc000000000000000000000000000000000000000000000000000000000000001";
+
+    #[test]
+    fn test_compact_roundtrip() {
+        let compact = hex::decode(COMPACT_PROOF).unwrap();
+        let seal = from_compact_groth16_proof(&compact).unwrap();
+        assert_eq!(seal.as_bytes().len(), GROTH16_POINTS_SIZE);
+
+        let proof = Groth16Proof::new(seal, [0xff; 32]);
+        let canonical = to_canonical_groth16_proof(&proof, &[1; 32]).unwrap();
+        assert_eq!(canonical.public_inputs().len(), 5);
+        assert_eq!(
+            to_compact_groth16_proof(&proof, &[1; 32]).unwrap()[..],
+            compact
+        );
+        assert!(from_compact_groth16_proof(&compact[1..]).is_err());
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use risc0_zkvm::{serde::to_vec, Receipt};
@@ -0,0 +1,46 @@
+//! Setup overhead of proving with `SP1Host` in mock mode.
+//!
+//! Compares proving with a host that reuses its prover client against creating a prover client
+//! for every proof.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use sp1_sdk::{Prover, ProverClient, SP1ProofMode};
+use strata_sp1_adapter::{SP1Host, SP1ProofInputBuilder, SP1ProverInput};
+use strata_zkvm::{ProofType, ZkVmHost, ZkVmInputBuilder};
+
+// Same guest as the unit tests: reads a `u32` and commits it
+const TEST_ELF: &[u8] = include_bytes!("../tests/elf/riscv32im-succinct-zkvm-elf");
+
+fn input() -> SP1ProverInput {
+    SP1ProofInputBuilder::new()
+        .write_serde(&1u32)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+fn bench_host(c: &mut Criterion) {
+    std::env::set_var("SP1_PROVER", "mock");
+
+    let (proving_key, _) = ProverClient::from_env().setup(TEST_ELF);
+    let host = SP1Host::new(proving_key.clone());
+
+    c.bench_function("sp1_host_clone", |b| b.iter(|| host.clone()));
+
+    c.bench_function("sp1_mock_prove_shared_client", |b| {
+        b.iter(|| host.prove(input(), ProofType::Core).unwrap())
+    });
+
+    // Only the client is created per proof, the proving key is shared as in the host
+    c.bench_function("sp1_mock_prove_fresh_client", |b| {
+        b.iter(|| {
+            let client = ProverClient::builder().mock().build();
+            client
+                .prove(&proving_key, input().stdin(), SP1ProofMode::Core)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_host);
+criterion_main!(benches);
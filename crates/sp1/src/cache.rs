@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, SP1ProvingKey, SP1VerifyingKey, SP1_CIRCUIT_VERSION};
+use strata_zkvm::{ZkVmError, ZkVmResult};
+use tracing::warn;
+
+/// Counter making the names of the temporary files unique across the writes of a process.
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A cache entry as stored on disk, prefixed by the SHA-256 checksum of its serialization.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    circuit_version: String,
+    elf_hash: [u8; 32],
+    proving_key: Vec<u8>,
+    verifying_key: Vec<u8>,
+}
+
+/// An on-disk cache of SP1 proving keys.
+///
+/// Entries are content-addressed by the hash of the ELF and the SP1 circuit version, so that a
+/// new guest build or an SP1 upgrade never picks up a stale key. Each entry stores the serialized
+/// proving and verifying keys along with a checksum, and entries that fail validation on load are
+/// treated as cache misses.
+#[derive(Debug, Clone)]
+pub struct SP1KeyCache {
+    dir: PathBuf,
+}
+
+impl SP1KeyCache {
+    /// Creates a cache storing its entries in `dir`. The directory is created on the first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the path of the cache entry for the given ELF.
+    pub fn entry_path(&self, elf: &[u8]) -> PathBuf {
+        let elf_hash = hex::encode(Sha256::digest(elf));
+        self.dir
+            .join(format!("{SP1_CIRCUIT_VERSION}-{elf_hash}.pk"))
+    }
+
+    /// Loads the proving key for the given ELF, if a valid entry is cached.
+    pub fn load(&self, elf: &[u8]) -> Option<SP1ProvingKey> {
+        let path = self.entry_path(elf);
+        let bytes = fs::read(&path).ok()?;
+        match decode_entry(&bytes, elf) {
+            Ok(proving_key) => Some(proving_key),
+            Err(err) => {
+                warn!(path = %path.display(), %err, "ignoring invalid sp1 proving key cache entry");
+                None
+            }
+        }
+    }
+
+    /// Stores the proving key for the given ELF, replacing any existing entry.
+    pub fn store(&self, elf: &[u8], proving_key: &SP1ProvingKey) -> ZkVmResult<()> {
+        let entry = CacheEntry {
+            circuit_version: SP1_CIRCUIT_VERSION.to_string(),
+            elf_hash: Sha256::digest(elf).into(),
+            proving_key: bincode::serialize(proving_key).map_err(cache_error)?,
+            verifying_key: bincode::serialize(&proving_key.vk).map_err(cache_error)?,
+        };
+        let payload = bincode::serialize(&entry).map_err(cache_error)?;
+        let bytes = [Sha256::digest(&payload).as_slice(), &payload].concat();
+
+        // Write to a temporary file first so that concurrent readers never see a partial entry
+        fs::create_dir_all(&self.dir).map_err(cache_error)?;
+        let path = self.entry_path(elf);
+        let tmp_path = path.with_extension(format!(
+            "tmp-{}-{}",
+            std::process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, bytes).map_err(cache_error)?;
+        fs::rename(&tmp_path, &path).map_err(cache_error)
+    }
+
+    /// Returns the directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+fn decode_entry(bytes: &[u8], elf: &[u8]) -> Result<SP1ProvingKey, String> {
+    if bytes.len() < 32 {
+        return Err("truncated entry".to_string());
+    }
+    let (checksum, payload) = bytes.split_at(32);
+    if Sha256::digest(payload).as_slice() != checksum {
+        return Err("checksum mismatch".to_string());
+    }
+
+    let entry: CacheEntry = bincode::deserialize(payload).map_err(|e| e.to_string())?;
+    if entry.circuit_version != SP1_CIRCUIT_VERSION {
+        return Err(format!("circuit version {}", entry.circuit_version));
+    }
+    if entry.elf_hash != <[u8; 32]>::from(Sha256::digest(elf)) {
+        return Err("elf hash mismatch".to_string());
+    }
+
+    let proving_key: SP1ProvingKey =
+        bincode::deserialize(&entry.proving_key).map_err(|e| e.to_string())?;
+    let verifying_key: SP1VerifyingKey =
+        bincode::deserialize(&entry.verifying_key).map_err(|e| e.to_string())?;
+    if proving_key.elf != elf {
+        return Err("proving key elf mismatch".to_string());
+    }
+    if proving_key.vk.bytes32() != verifying_key.bytes32() {
+        return Err("verifying key mismatch".to_string());
+    }
+
+    Ok(proving_key)
+}
+
+fn cache_error(err: impl std::fmt::Display) -> ZkVmError {
+    ZkVmError::Other(format!("sp1 proving key cache: {err}"))
+}
@@ -4,7 +4,7 @@ use sha2::{Digest, Sha256};
 use sp1_zkvm::io;
 #[cfg(not(feature = "mock"))]
 use sp1_zkvm::lib::verify::verify_sp1_proof;
-use strata_zkvm::{Proof, ZkVmEnv};
+use strata_zkvm::{ProgramId, Proof, ZkVmEnv};
 
 #[cfg(not(feature = "mock"))]
 use crate::verify_groth16;
@@ -29,13 +29,13 @@ impl ZkVmEnv for Sp1ZkVmEnv {
     }
 
     #[cfg(not(feature = "mock"))]
-    fn verify_native_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]) {
+    fn verify_native_proof(&self, program_id: &ProgramId, public_values: &[u8]) {
         let pv_digest = Sha256::digest(public_values);
-        verify_sp1_proof(vk_digest, &pv_digest.into());
+        verify_sp1_proof(program_id.as_words(), &pv_digest.into());
     }
 
     #[cfg(feature = "mock")]
-    fn verify_native_proof(&self, _vk_digest: &[u32; 8], _public_values: &[u8]) {}
+    fn verify_native_proof(&self, _program_id: &ProgramId, _public_values: &[u8]) {}
 
     #[cfg(not(feature = "mock"))]
     fn verify_groth16_proof(
@@ -56,8 +56,8 @@ impl ZkVmEnv for Sp1ZkVmEnv {
     ) {
     }
 
-    fn read_verified_serde<T: DeserializeOwned>(&self, vk_digest: &[u32; 8]) -> T {
-        let buf = self.read_verified_buf(vk_digest);
+    fn read_verified_serde<T: DeserializeOwned>(&self, program_id: &ProgramId) -> T {
+        let buf = self.read_verified_buf(program_id);
         bincode::deserialize(&buf).expect("bincode deserialization failed")
     }
 }
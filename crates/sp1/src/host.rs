@@ -1,41 +1,183 @@
-use std::fmt;
+use std::{
+    any::Any,
+    fmt, panic,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
 
 use serde::{de::DeserializeOwned, Serialize};
-use sp1_sdk::{HashableKey, ProverClient, SP1ProvingKey};
+use sha2::{Digest, Sha256};
+use sp1_prover::components::CpuProverComponents;
+use sp1_sdk::{
+    HashableKey, Prover, ProverClient, SP1ProofMode, SP1ProvingKey, SP1_CIRCUIT_VERSION,
+};
 use strata_zkvm::{
-    ProofType, PublicValues, VerificationKey, ZkVmError, ZkVmHost, ZkVmInputBuilder, ZkVmResult,
+    validate_elf, Groth16Proof, ProgramId, ProofReceipt, ProofType, ProveRequest, ProverBackend,
+    PublicValues, VerificationKey, ZkVm, ZkVmElfError, ZkVmError, ZkVmHost, ZkVmInputBuilder,
+    ZkVmResult, ZkVmVerificationKeyError,
+};
+use tracing::warn;
+
+use crate::{
+    cache::SP1KeyCache, extract_groth16_seal, input::SP1ProofInputBuilder, proof::SP1ProofReceipt,
 };
 
-use crate::{input::SP1ProofInputBuilder, proof::SP1ProofReceipt};
+/// Maximum size of a guest ELF accepted by [`SP1Host::try_init`].
+const MAX_ELF_SIZE: usize = 1 << 28;
+
+/// An SP1 prover client of any kind.
+type SP1Client = Box<dyn Prover<CpuProverComponents>>;
 
 /// A host for the `SP1` zkVM that stores the guest program in ELF format.
 /// The `SP1Host` is responsible for program execution and proving
+///
+/// Proofs are generated with the host's [`ProverBackend`], which defaults to
+/// [`ProverBackend::Env`], or to [`ProverBackend::Mock`] with the `mock` feature. Clones of a host
+/// share its proving key and its prover client, which is created on first use.
 #[derive(Clone)]
 pub struct SP1Host {
-    proving_key: SP1ProvingKey,
+    proving_key: Arc<SP1ProvingKey>,
+    verification_key: VerificationKey,
+    backend: ProverBackend,
+    client: Arc<OnceLock<SP1Client>>,
 }
 
 impl SP1Host {
     /// Creates a new instance of [`SP1Host`] using the provided [`SP1ProvingKey`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the verification key cannot be serialized. See [`SP1Host::try_new`].
     pub fn new(proving_key: SP1ProvingKey) -> Self {
-        Self { proving_key }
+        Self::try_new(proving_key).expect("invalid sp1 vk")
+    }
+
+    /// Creates a new instance of [`SP1Host`] using the provided [`SP1ProvingKey`].
+    pub fn try_new(proving_key: SP1ProvingKey) -> ZkVmResult<Self> {
+        let verification_key = bincode::serialize(&proving_key.vk)
+            .map_err(|e| ZkVmVerificationKeyError::DataFormat(e.into()))?;
+        Ok(Self {
+            proving_key: Arc::new(proving_key),
+            verification_key: VerificationKey::new(verification_key),
+            backend: default_backend(),
+            client: Arc::default(),
+        })
     }
 
     /// Creates a new instance of [`SP1Host`] from serialized proving key bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not a valid proving key. See [`SP1Host::try_new_from_bytes`].
     pub fn new_from_bytes(proving_key_bytes: &[u8]) -> Self {
-        let proving_key: SP1ProvingKey =
-            bincode::deserialize(proving_key_bytes).expect("invalid sp1 pk bytes");
-        SP1Host::new(proving_key)
+        Self::try_new_from_bytes(proving_key_bytes).expect("invalid sp1 pk bytes")
+    }
+
+    /// Creates a new instance of [`SP1Host`] from serialized proving key bytes.
+    pub fn try_new_from_bytes(proving_key_bytes: &[u8]) -> ZkVmResult<Self> {
+        let proving_key: SP1ProvingKey = bincode::deserialize(proving_key_bytes)
+            .map_err(|e| ZkVmVerificationKeyError::DataFormat(e.into()))?;
+        Self::try_new(proving_key)
     }
 
     /// Initializes a new [`SP1Host`] by setting up the proving key using the provided ELF bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ELF is invalid. See [`SP1Host::try_init`].
     pub fn init(elf: &[u8]) -> Self {
-        let client = ProverClient::from_env();
-        let (proving_key, _) = client.setup(elf);
-        Self { proving_key }
+        Self::try_init(elf).expect("invalid elf")
+    }
+
+    /// Initializes a new [`SP1Host`] by setting up the proving key using the provided ELF bytes.
+    ///
+    /// The ELF is validated with [`validate_elf`] before the setup, which is useful when the ELF
+    /// is loaded at runtime.
+    pub fn try_init(elf: &[u8]) -> ZkVmResult<Self> {
+        validate_elf(elf, MAX_ELF_SIZE)?;
+
+        // The SP1 setup panics instead of returning an error on ELFs it cannot load
+        let client = new_prover_client(&default_backend());
+        let setup = panic::catch_unwind(panic::AssertUnwindSafe(|| client.setup(elf)));
+        let (proving_key, _) = setup.map_err(|e| ZkVmElfError::Other(panic_message(&*e)))?;
+
+        // Keep the client used for the setup for proving
+        let host = Self::try_new(proving_key)?;
+        let _ = host.client.set(client);
+        Ok(host)
+    }
+
+    /// Initializes a new [`SP1Host`] like [`SP1Host::try_init`], reusing the proving key cached
+    /// in `cache_dir` if there is a valid one.
+    ///
+    /// The proving key is stored in the cache after a setup. Failing to store it is logged but
+    /// does not fail the initialization. See [`SP1KeyCache`] for how entries are keyed and
+    /// validated.
+    pub fn init_cached(elf: &[u8], cache_dir: impl AsRef<Path>) -> ZkVmResult<Self> {
+        let cache = SP1KeyCache::new(cache_dir.as_ref());
+        if let Some(proving_key) = cache.load(elf) {
+            return Self::try_new(proving_key);
+        }
+
+        let host = Self::try_init(elf)?;
+        if let Err(err) = cache.store(elf, &host.proving_key) {
+            warn!(%err, "failed to cache sp1 proving key");
+        }
+        Ok(host)
+    }
+
+    /// Returns the host with proofs generated by `backend`.
+    ///
+    /// With [`ProverBackend::ExternalProcess`] and [`ProverBackend::Remote`], programs are still
+    /// executed and proofs verified with a prover client configured from the environment.
+    pub fn with_backend(mut self, backend: ProverBackend) -> Self {
+        if backend != self.backend {
+            self.backend = backend;
+            self.client = Arc::default();
+        }
+        self
+    }
+
+    /// Returns the prover backend of the host.
+    pub fn backend(&self) -> &ProverBackend {
+        &self.backend
+    }
+
+    /// Returns the prover client of the host, creating it on first use.
+    pub(crate) fn client(&self) -> &dyn Prover<CpuProverComponents> {
+        self.client
+            .get_or_init(|| new_prover_client(&self.backend))
+            .as_ref()
     }
 }
 
+fn default_backend() -> ProverBackend {
+    if cfg!(feature = "mock") {
+        ProverBackend::Mock
+    } else {
+        ProverBackend::Env
+    }
+}
+
+/// Creates the prover client used to set up, execute, prove and verify with `backend`.
+fn new_prover_client(backend: &ProverBackend) -> SP1Client {
+    match backend {
+        ProverBackend::Local => Box::new(ProverClient::builder().cpu().build()),
+        ProverBackend::Mock => Box::new(ProverClient::builder().mock().build()),
+        ProverBackend::Env
+        | ProverBackend::ExternalProcess { .. }
+        | ProverBackend::Remote { .. } => Box::new(ProverClient::from_env()),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "sp1 setup failed".to_string())
+}
+
 impl ZkVmHost for SP1Host {
     type Input<'a> = SP1ProofInputBuilder;
     type ZkVmProofReceipt = SP1ProofReceipt;
@@ -44,28 +186,44 @@ impl ZkVmHost for SP1Host {
         prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
         proof_type: ProofType,
     ) -> ZkVmResult<SP1ProofReceipt> {
-        #[cfg(feature = "mock")]
-        {
-            std::env::set_var("SP1_PROVER", "mock");
+        if self.backend.is_delegated() {
+            let request = ProveRequest {
+                zkvm: ZkVm::SP1,
+                elf: self.get_elf().to_vec(),
+                input: prover_input.recorded,
+                proof_type,
+            };
+            let receipt = self.backend.prove_delegated(&request)?;
+            return Ok(SP1ProofReceipt::try_from(&receipt)?);
         }
 
-        let client = ProverClient::from_env();
-
         // Start proving
-        let mut prover = client.prove(&self.proving_key, &prover_input);
-        prover = match proof_type {
-            ProofType::Compressed => prover.compressed(),
-            ProofType::Core => prover.core(),
-            ProofType::Groth16 => prover.groth16(),
+        let mode = match proof_type {
+            ProofType::Compressed => SP1ProofMode::Compressed,
+            ProofType::Core => SP1ProofMode::Core,
+            ProofType::Groth16 => SP1ProofMode::Groth16,
         };
 
-        let proof_info = prover
-            .run()
+        let proof_info = self
+            .client()
+            .prove(&self.proving_key, &prover_input.stdin, mode)
             .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?;
 
         Ok(proof_info.into())
     }
 
+    fn execute<'a>(
+        &self,
+        prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+    ) -> ZkVmResult<PublicValues> {
+        let (public_values, _) = self
+            .client()
+            .execute(self.get_elf(), &prover_input.stdin)
+            .map_err(|e| ZkVmError::ExecutionError(e.to_string()))?;
+
+        Ok(PublicValues::new(public_values.to_vec()))
+    }
+
     fn extract_serde_public_output<T: Serialize + DeserializeOwned>(
         public_values: &PublicValues,
     ) -> ZkVmResult<T> {
@@ -74,18 +232,40 @@ impl ZkVmHost for SP1Host {
         Ok(public_params)
     }
 
+    fn extract_groth16_proof(receipt: &ProofReceipt) -> ZkVmResult<Groth16Proof> {
+        // `SP1ProofWithPublicValues::bytes` panics on an invalid encoded proof
+        let seal = extract_groth16_seal(receipt.proof())?;
+        let public_values_digest = Sha256::digest(receipt.public_values().as_bytes()).into();
+        Ok(Groth16Proof::new(seal, public_values_digest))
+    }
+
     fn get_elf(&self) -> &[u8] {
         &self.proving_key.elf
     }
 
+    fn backend_version(&self) -> String {
+        format!("sp1-{SP1_CIRCUIT_VERSION}")
+    }
+
     fn get_verification_key(&self) -> VerificationKey {
-        let verification_key = bincode::serialize(&self.proving_key.vk).unwrap();
-        VerificationKey::new(verification_key)
+        self.verification_key.clone()
+    }
+
+    fn program_id(&self) -> ProgramId {
+        ProgramId::new(self.proving_key.vk.hash_u32())
+    }
+
+    fn get_groth16_verification_key(&self) -> [u8; 32] {
+        // SP1 Groth16 proofs commit to the BN254 hash of the vk, which is only exposed as a
+        // `0x`-prefixed hex string
+        let vk_hash = self.proving_key.vk.bytes32();
+        let mut vk_hash_bytes = [0u8; 32];
+        hex::decode_to_slice(&vk_hash[2..], &mut vk_hash_bytes).expect("valid vk hash");
+        vk_hash_bytes
     }
 
     fn verify_inner(&self, proof: &SP1ProofReceipt) -> ZkVmResult<()> {
-        let client = ProverClient::from_env();
-        client
+        self.client()
             .verify(proof.as_ref(), &self.proving_key.vk)
             .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))?;
 
@@ -104,10 +284,11 @@ impl fmt::Display for SP1Host {
 #[cfg(not(debug_assertions))]
 mod tests {
 
-    use std::{fs::File, io::Write};
-
     use sp1_sdk::HashableKey;
-    use strata_zkvm::{ProofType, ZkVmHost};
+    use strata_zkvm::{
+        DynZkVmHost, ProofFixtures, ProofType, PublicValues, StandInProverServer, ZkVmHost,
+        ZkVmInputResult, ZkVmProver,
+    };
 
     use super::*;
 
@@ -120,6 +301,59 @@ mod tests {
     // }
     const TEST_ELF: &[u8] = include_bytes!("../tests/elf/riscv32im-succinct-zkvm-elf");
 
+    #[test]
+    fn test_try_init_rejects_invalid_elf() {
+        let res = SP1Host::try_init(b"not an elf");
+        assert!(matches!(
+            res,
+            Err(ZkVmError::InvalidELF(ZkVmElfError::NotElf))
+        ));
+
+        let res = SP1Host::try_new_from_bytes(b"not a proving key");
+        assert!(matches!(res, Err(ZkVmError::InvalidVerificationKey(_))));
+
+        let host = SP1Host::try_init(TEST_ELF).unwrap();
+        let bytes = bincode::serialize(&*host.proving_key).unwrap();
+        let host_from_bytes = SP1Host::try_new_from_bytes(&bytes).unwrap();
+        assert_eq!(
+            host.get_verification_key(),
+            host_from_bytes.get_verification_key()
+        );
+    }
+
+    #[test]
+    fn test_init_cached() {
+        let cache_dir = std::env::temp_dir().join(format!("sp1-pk-cache-{}", std::process::id()));
+        let cache = SP1KeyCache::new(&cache_dir);
+        let entry_path = cache.entry_path(TEST_ELF);
+
+        let host = SP1Host::init_cached(TEST_ELF, &cache_dir).unwrap();
+        assert!(entry_path.exists());
+        assert!(cache.load(TEST_ELF).is_some());
+
+        let cached_host = SP1Host::init_cached(TEST_ELF, &cache_dir).unwrap();
+        assert_eq!(
+            host.get_verification_key(),
+            cached_host.get_verification_key()
+        );
+
+        // A corrupted entry is ignored and replaced
+        let mut bytes = std::fs::read(&entry_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        std::fs::write(&entry_path, bytes).unwrap();
+        assert!(cache.load(TEST_ELF).is_none());
+
+        let host_after_corruption = SP1Host::init_cached(TEST_ELF, &cache_dir).unwrap();
+        assert_eq!(
+            host.get_verification_key(),
+            host_after_corruption.get_verification_key()
+        );
+        assert!(cache.load(TEST_ELF).is_some());
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
     #[test]
     fn test_mock_prover() {
         let input: u32 = 1;
@@ -145,6 +379,68 @@ mod tests {
         assert_eq!(input, out)
     }
 
+    #[test]
+    fn test_remote_backend() {
+        // The stand-in server proves with a mock SP1 host, like a remote prover would
+        let server = StandInProverServer::start(|request: ProveRequest| {
+            let host = SP1Host::try_init(&request.elf)?.with_backend(ProverBackend::Mock);
+            host.prove_recorded(&request.input, request.proof_type)
+        })
+        .unwrap();
+        let host = SP1Host::init(TEST_ELF).with_backend(ProverBackend::Remote {
+            endpoint: server.endpoint().to_string(),
+        });
+
+        let input: u32 = 3;
+        let prover_input = SP1ProofInputBuilder::new()
+            .write_serde(&input)
+            .unwrap()
+            .build()
+            .unwrap();
+        let proof = host.prove(prover_input, ProofType::Core).unwrap();
+        let out: u32 = SP1Host::extract_serde_public_output(proof.public_values()).unwrap();
+        assert_eq!(input, out);
+    }
+
+    /// Proves the `TEST_ELF` program, which commits its input.
+    struct EchoProver;
+
+    impl ZkVmProver for EchoProver {
+        type Input = u32;
+        type Output = u32;
+
+        fn proof_type() -> ProofType {
+            ProofType::Core
+        }
+
+        fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+        where
+            B: ZkVmInputBuilder<'a>,
+        {
+            B::new().write_serde(input)?.build()
+        }
+
+        fn process_output<H>(public_values: &PublicValues) -> ZkVmResult<Self::Output>
+        where
+            H: ZkVmHost,
+        {
+            H::extract_serde_public_output(public_values)
+        }
+    }
+
+    #[test]
+    fn test_mock_prover_batch() {
+        let host = SP1Host::init(TEST_ELF).with_backend(ProverBackend::Mock);
+        let inputs = [1, 2, 3, 4];
+        let results = EchoProver::prove_batch(&inputs, &host, 2);
+        for (input, res) in inputs.iter().zip(results) {
+            let receipt = res.unwrap();
+            host.verify(&receipt).unwrap();
+            let out = EchoProver::process_output::<SP1Host>(receipt.public_values()).unwrap();
+            assert_eq!(*input, out);
+        }
+    }
+
     #[test]
     fn test_groth16_proof_generation() {
         sp1_sdk::utils::setup_logger();
@@ -166,13 +462,82 @@ mod tests {
 
         // Note: For the fixed ELF and fixed SP1 version, the vk is fixed
         assert_eq!(
-            zkvm.verifying_key.bytes32(),
+            zkvm.proving_key.vk.bytes32(),
             "0x00efb1120491119751e75bc55bc95b64d33f973ecf68fcf5cbff08506c5788f9"
         );
+        assert_eq!(
+            hex::encode(zkvm.get_groth16_verification_key()),
+            "00efb1120491119751e75bc55bc95b64d33f973ecf68fcf5cbff08506c5788f9"
+        );
 
-        let filename = "proof-groth16.bin";
-        let mut file = File::create(filename).unwrap();
-        file.write_all(&bincode::serialize(&proof).expect("bincode serialization failed"))
-            .unwrap();
+        // assert the extracted Groth16 proof verifies with the crate's own verifier
+        let groth16_proof = SP1Host::extract_groth16_proof(&proof).unwrap();
+        crate::verify_groth16(
+            groth16_proof.seal(),
+            &zkvm.get_groth16_verification_key(),
+            proof.public_values().as_bytes(),
+        )
+        .expect("Groth16 proof verification failed");
+
+        // assert the canonical encodings round-trip to a verifiable proof
+        let canonical =
+            crate::to_canonical_groth16_proof(&groth16_proof, &zkvm.get_groth16_verification_key())
+                .unwrap();
+        let calldata = canonical.to_solidity_calldata();
+        let decoded =
+            strata_zkvm::CanonicalGroth16Proof::from_solidity_calldata(&calldata).unwrap();
+        assert_eq!(decoded, canonical);
+        let seal = crate::from_canonical_groth16_proof(&decoded);
+        assert_eq!(&seal, groth16_proof.seal());
+        crate::verify_groth16(
+            &seal,
+            &zkvm.get_groth16_verification_key(),
+            proof.public_values().as_bytes(),
+        )
+        .expect("Groth16 proof verification failed");
+    }
+
+    /// The golden proofs of `TEST_ELF` with input `1`, regenerated with `UPDATE_FIXTURES=1`.
+    fn fixtures() -> ProofFixtures {
+        ProofFixtures::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+    }
+
+    #[test]
+    #[ignore = "the fixtures in tests/fixtures are not generated yet, see the README"]
+    fn test_golden_fixtures() {
+        let input: u32 = 1;
+        let fixtures = fixtures();
+        let host = SP1Host::init(TEST_ELF).with_backend(ProverBackend::Local);
+
+        // Fails whenever the ELF or the SP1 version changes the keys
+        let snapshot = fixtures.check_vk_snapshot(&host).unwrap();
+
+        for proof_type in [ProofType::Core, ProofType::Compressed, ProofType::Groth16] {
+            let receipt = fixtures
+                .receipt(proof_type, || {
+                    let prover_input = SP1ProofInputBuilder::new()
+                        .write_serde(&input)
+                        .unwrap()
+                        .build()
+                        .unwrap();
+                    host.prove(prover_input, proof_type)
+                })
+                .unwrap();
+
+            host.verify(&receipt)
+                .unwrap_or_else(|e| panic!("golden {proof_type:?} proof: {e}"));
+            let out: u32 = SP1Host::extract_serde_public_output(receipt.public_values()).unwrap();
+            assert_eq!(input, out);
+        }
+
+        // The golden Groth16 proof verifies with the crate's own verifier
+        let receipt = fixtures.load_receipt(ProofType::Groth16).unwrap();
+        let groth16_proof = SP1Host::extract_groth16_proof(&receipt).unwrap();
+        crate::verify_groth16(
+            groth16_proof.seal(),
+            &snapshot.groth16_verification_key,
+            receipt.public_values().as_bytes(),
+        )
+        .expect("golden Groth16 proof verification failed");
     }
 }
@@ -1,34 +1,66 @@
-use sp1_sdk::{SP1Proof, SP1Stdin, SP1VerifyingKey};
+use sp1_sdk::{HashableKey, SP1Proof, SP1Stdin, SP1VerifyingKey};
 use strata_zkvm::{
-    AggregationInput, ProofType, ZkVmInputBuilder, ZkVmInputError, ZkVmInputResult, ZkVmProofError,
-    ZkVmVerificationKeyError,
+    AggregationInput, ProofType, RecordedInput, ZkVmInputBuilder, ZkVmInputError,
+    ZkVmInputRecorder, ZkVmInputResult, ZkVmProofError, ZkVmVerificationKeyError,
 };
 
 use crate::proof::SP1ProofReceipt;
 
+/// The input of an SP1 program.
+///
+/// Besides the [`SP1Stdin`] proven locally, it keeps the items it was built from as a
+/// [`RecordedInput`], which is what delegated prover backends are sent.
+#[derive(Debug, Clone)]
+pub struct SP1ProverInput {
+    pub(crate) stdin: SP1Stdin,
+    pub(crate) recorded: RecordedInput,
+}
+
+impl SP1ProverInput {
+    /// Returns the stdin passed to the SP1 prover.
+    pub fn stdin(&self) -> &SP1Stdin {
+        &self.stdin
+    }
+
+    /// Returns the items the input was built from.
+    pub fn recorded(&self) -> &RecordedInput {
+        &self.recorded
+    }
+}
+
 // A wrapper around SP1Stdin
-pub struct SP1ProofInputBuilder(SP1Stdin);
+pub struct SP1ProofInputBuilder {
+    stdin: SP1Stdin,
+    recorder: ZkVmInputRecorder,
+}
 
 impl ZkVmInputBuilder<'_> for SP1ProofInputBuilder {
-    type Input = SP1Stdin;
+    type Input = SP1ProverInput;
     type ZkVmProofReceipt = SP1ProofReceipt;
 
     fn new() -> SP1ProofInputBuilder {
-        SP1ProofInputBuilder(SP1Stdin::new())
+        SP1ProofInputBuilder {
+            stdin: SP1Stdin::new(),
+            recorder: ZkVmInputRecorder::new(),
+        }
     }
 
     fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
-        self.0.write(item);
+        self.stdin.write(item);
+        self.recorder.write_serde(item)?;
         Ok(self)
     }
 
     fn write_borsh<T: borsh::BorshSerialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
         let slice = borsh::to_vec(item).map_err(|e| ZkVmInputError::DataFormat(e.into()))?;
-        self.write_buf(&slice)
+        self.stdin.write_slice(&slice);
+        self.recorder.write_borsh(item)?;
+        Ok(self)
     }
 
     fn write_buf(&mut self, item: &[u8]) -> ZkVmInputResult<&mut Self> {
-        self.0.write_slice(item);
+        self.stdin.write_slice(item);
+        self.recorder.write_buf(item)?;
         Ok(self)
     }
 
@@ -37,34 +69,96 @@ impl ZkVmInputBuilder<'_> for SP1ProofInputBuilder {
             .receipt()
             .try_into()
             .map_err(ZkVmInputError::ProofReceipt)?;
+        // SP1 needs the full verification key to verify the proof recursively, the program id
+        // alone is not enough. Make sure that both identify the same program.
         let vkey: SP1VerifyingKey = bincode::deserialize(item.vk().as_bytes())
             .map_err(|e| ZkVmVerificationKeyError::DataFormat(e.into()))
             .map_err(ZkVmInputError::VerificationKey)?;
+        if vkey.hash_u32() != *item.program_id().as_words() {
+            return Err(ZkVmInputError::VerificationKey(
+                ZkVmVerificationKeyError::ProgramIdMismatch(item.program_id()),
+            ));
+        }
+
+        let SP1Proof::Compressed(compressed_proof) = receipt.inner().proof else {
+            return Err(ZkVmInputError::ProofReceipt(
+                ZkVmProofError::InvalidProofType(ProofType::Compressed),
+            ));
+        };
 
         // Write the public values of the program that'll be proven inside zkVM.
-        self.0
-            .write_slice(receipt.as_ref().public_values.as_slice());
+        self.stdin
+            .write_slice(item.receipt().public_values().as_bytes());
 
         // Write the proofs.
         //
         // Note: this data will not actually be read by the aggregation program, instead it will
         // be witnessed by the prover during the recursive aggregation process
         // inside SP1 itself.
-        match receipt.inner().proof {
-            SP1Proof::Compressed(compressed_proof) => {
-                self.0.write_proof(*compressed_proof, vkey.vk);
-            }
-            _ => {
-                return Err(ZkVmInputError::ProofReceipt(
-                    ZkVmProofError::InvalidProofType(ProofType::Compressed),
-                ))
-            }
-        }
+        self.stdin.write_proof(*compressed_proof, vkey.vk);
 
+        self.recorder.write_proof(item)?;
         Ok(self)
     }
 
+    fn aggregated_proof_type() -> ProofType {
+        // Only compressed proofs can be verified recursively
+        ProofType::Compressed
+    }
+
     fn build(&mut self) -> ZkVmInputResult<Self::Input> {
-        Ok(self.0.clone())
+        Ok(SP1ProverInput {
+            stdin: self.stdin.clone(),
+            recorded: self.recorder.build()?,
+        })
+    }
+}
+
+// NOTE: SP1 prover runs in release mode only; therefore run the tests on release mode only
+#[cfg(test)]
+#[cfg(not(debug_assertions))]
+mod tests {
+    use strata_zkvm::ZkVmHost;
+
+    use super::*;
+    use crate::SP1Host;
+
+    const TEST_ELF: &[u8] = include_bytes!("../tests/elf/riscv32im-succinct-zkvm-elf");
+
+    #[test]
+    fn test_write_proof_frame_layout() {
+        let host = SP1Host::init(TEST_ELF);
+        let child_input = SP1ProofInputBuilder::new()
+            .write_serde(&1u32)
+            .unwrap()
+            .build()
+            .unwrap();
+        let receipt = host.prove(child_input, ProofType::Compressed).unwrap();
+        let item = AggregationInput::new(
+            receipt.clone(),
+            host.program_id(),
+            host.get_verification_key(),
+        );
+
+        let input = SP1ProofInputBuilder::new()
+            .write_serde(&7u32)
+            .unwrap()
+            .write_proof(&item)
+            .unwrap()
+            .write_buf(&[9, 9])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // A proof input contributes exactly one frame holding its public values, the proof itself
+        // is passed as a deferred proof.
+        let expected = vec![
+            bincode::serialize(&7u32).unwrap(),
+            receipt.public_values().as_bytes().to_vec(),
+            vec![9, 9],
+        ];
+        assert_eq!(input.stdin().buffer, expected);
+        assert_eq!(input.stdin().proofs.len(), 1);
+        assert_eq!(input.recorded().items().len(), 3);
     }
 }
@@ -1,3 +1,12 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "prover")]
+mod cache;
+#[cfg(feature = "prover")]
+pub use cache::SP1KeyCache;
+
 #[cfg(feature = "prover")]
 mod host;
 #[cfg(feature = "prover")]
@@ -8,7 +17,9 @@ mod input;
 #[cfg(feature = "prover")]
 mod proof;
 #[cfg(feature = "prover")]
-pub use input::SP1ProofInputBuilder;
+pub use input::{SP1ProofInputBuilder, SP1ProverInput};
+#[cfg(feature = "prover")]
+pub use proof::SP1ProofReceipt;
 
 #[cfg(feature = "zkvm")]
 mod env;
@@ -18,5 +29,10 @@ pub use env::Sp1ZkVmEnv;
 #[cfg(feature = "perf")]
 pub mod perf;
 
+#[cfg(feature = "receipt")]
+mod receipt;
+#[cfg(feature = "receipt")]
+pub use receipt::extract_groth16_seal;
+
 mod verifier;
 pub use verifier::*;
@@ -1,4 +1,3 @@
-use sp1_sdk::ProverClient;
 use strata_zkvm::{ProofReport, ProofType, ZkVmHost, ZkVmHostPerf, ZkVmInputBuilder, ZkVmResult};
 
 use crate::SP1Host;
@@ -10,11 +9,12 @@ impl ZkVmHostPerf for SP1Host {
         _proof_type: ProofType,
         report_name: String,
     ) -> ZkVmResult<ProofReport> {
-        let client = ProverClient::from_env();
-
         std::env::set_var("TRACE_FILE", format!("{}.trace", report_name));
 
-        let (_, report) = client.execute(self.get_elf(), &input).run().unwrap();
+        let (_, report) = self
+            .client()
+            .execute(self.get_elf(), input.stdin())
+            .unwrap();
 
         Ok(ProofReport {
             cycles: report.total_instruction_count(),
@@ -1,5 +1,5 @@
 use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1PublicValues};
-use strata_zkvm::{Proof, ProofReceipt, PublicValues, ZkVmProofError};
+use strata_zkvm::{deserialize_proof, Proof, ProofReceipt, PublicValues, ZkVmProofError};
 
 #[derive(Debug, Clone)]
 pub struct SP1ProofReceipt(SP1ProofWithPublicValues);
@@ -33,8 +33,7 @@ impl TryFrom<&ProofReceipt> for SP1ProofReceipt {
     type Error = ZkVmProofError;
     fn try_from(value: &ProofReceipt) -> Result<Self, Self::Error> {
         let public_values = SP1PublicValues::from(value.public_values().as_bytes());
-        let proof: SP1Proof = bincode::deserialize(value.proof().as_bytes())
-            .map_err(|e| ZkVmProofError::DataFormat(e.into()))?;
+        let proof: SP1Proof = deserialize_proof(value.proof().as_bytes())?;
         let sp1_version = sp1_sdk::SP1_CIRCUIT_VERSION.to_string();
         let proof_receipt = SP1ProofWithPublicValues {
             proof,
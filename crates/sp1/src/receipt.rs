@@ -0,0 +1,141 @@
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+use strata_zkvm::{
+    deserialize_proof, Proof, ProofType, ZkVmProofError, ZkVmResult, GROTH16_SELECTOR_SIZE,
+};
+
+/// Index of the `Groth16` variant of `sp1_sdk::SP1Proof`.
+const SP1_PROOF_GROTH16_VARIANT: u32 = 3;
+
+/// Mirror of `sp1_sdk::Groth16Bn254Proof`, so that receipts can be decoded where `sp1-sdk` cannot
+/// be built, e.g. for wasm.
+///
+/// The field order must match upstream, as it defines the bincode encoding. The layout is checked
+/// against `sp1-sdk` by the tests of the `prover` feature.
+#[derive(Debug, Serialize, Deserialize)]
+struct Groth16Bn254Proof {
+    public_inputs: [String; 2],
+    encoded_proof: String,
+    raw_proof: String,
+    groth16_vkey_hash: [u8; 32],
+}
+
+/// Extracts the Groth16 seal expected by [`verify_groth16`](crate::verify_groth16) from the proof
+/// of a receipt produced by `SP1Host`, without depending on `sp1-sdk`.
+///
+/// This is the equivalent of `SP1ProofWithPublicValues::bytes` for Groth16 proofs, except that
+/// an invalid encoded proof is an error instead of a panic. Mock proofs have an empty seal.
+pub fn extract_groth16_seal(proof: &Proof) -> ZkVmResult<Proof> {
+    let proof = proof.as_bytes();
+    let variant = proof
+        .get(..4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("slice is 4 bytes")));
+    if variant != Some(SP1_PROOF_GROTH16_VARIANT) {
+        return Err(ZkVmProofError::InvalidProofType(ProofType::Groth16).into());
+    }
+
+    let groth16: Groth16Bn254Proof = deserialize_proof(&proof[4..])?;
+    if groth16.encoded_proof.is_empty() {
+        return Ok(Proof::new(Vec::new()));
+    }
+    let encoded_proof = hex::decode(&groth16.encoded_proof).map_err(|e| {
+        ZkVmProofError::InvalidGroth16Encoding(alloc::format!("invalid SP1 Groth16 proof: {e}"))
+    })?;
+
+    Ok(Proof::new(
+        [
+            &groth16.groth16_vkey_hash[..GROTH16_SELECTOR_SIZE],
+            &encoded_proof,
+        ]
+        .concat(),
+    ))
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use sp1_sdk::SP1Proof;
+    use strata_zkvm::{ProofReceipt, PublicValues, ZkVmError, ZkVmHost};
+
+    use super::*;
+    use crate::SP1Host;
+
+    fn sp1_groth16_proof(encoded_proof: &[u8]) -> Proof {
+        sp1_groth16_proof_with_encoding(hex::encode(encoded_proof))
+    }
+
+    fn sp1_groth16_proof_with_encoding(encoded_proof: String) -> Proof {
+        let proof = SP1Proof::Groth16(sp1_sdk::Groth16Bn254Proof {
+            public_inputs: ["1".into(), "2".into()],
+            encoded_proof,
+            raw_proof: "raw".into(),
+            groth16_vkey_hash: [7; 32],
+        });
+        Proof::new(bincode::serialize(&proof).unwrap())
+    }
+
+    #[test]
+    fn test_groth16_layout_matches_sp1_sdk() {
+        let seal = extract_groth16_seal(&sp1_groth16_proof(&[1; 256])).unwrap();
+        assert_eq!(
+            seal.as_bytes()[..GROTH16_SELECTOR_SIZE],
+            [7; GROTH16_SELECTOR_SIZE]
+        );
+        assert_eq!(seal.as_bytes()[GROTH16_SELECTOR_SIZE..], [1; 256]);
+
+        let mirror: Groth16Bn254Proof =
+            bincode::deserialize(&sp1_groth16_proof(&[1; 256]).as_bytes()[4..]).unwrap();
+        assert_eq!(mirror.public_inputs, ["1", "2"]);
+        assert_eq!(mirror.raw_proof, "raw");
+    }
+
+    #[test]
+    fn test_mock_groth16_proofs_have_an_empty_seal() {
+        let seal = extract_groth16_seal(&sp1_groth16_proof(&[])).unwrap();
+        assert!(seal.as_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_non_groth16_proofs_are_rejected() {
+        let proof = Proof::new(bincode::serialize(&SP1Proof::Core(Vec::new())).unwrap());
+        assert!(matches!(
+            extract_groth16_seal(&proof),
+            Err(ZkVmError::InvalidProofReceipt(
+                ZkVmProofError::InvalidProofType(ProofType::Groth16)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_groth16_encodings_are_rejected() {
+        // `SP1ProofWithPublicValues::bytes` panics on these
+        let proof = sp1_groth16_proof_with_encoding("zz".into());
+        assert!(matches!(
+            extract_groth16_seal(&proof),
+            Err(ZkVmError::InvalidProofReceipt(
+                ZkVmProofError::InvalidGroth16Encoding(_)
+            ))
+        ));
+
+        let receipt = ProofReceipt::new(proof, PublicValues::new(vec![1, 2, 3]));
+        assert!(matches!(
+            SP1Host::extract_groth16_proof(&receipt),
+            Err(ZkVmError::InvalidProofReceipt(
+                ZkVmProofError::InvalidGroth16Encoding(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_oversized_proofs_are_rejected() {
+        // A string length prefix larger than the decoding limit
+        let mut proof = SP1_PROOF_GROTH16_VARIANT.to_le_bytes().to_vec();
+        proof.extend_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            extract_groth16_seal(&Proof::new(proof)),
+            Err(ZkVmError::InvalidProofReceipt(ZkVmProofError::DataFormat(
+                _
+            )))
+        ));
+    }
+}
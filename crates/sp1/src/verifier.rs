@@ -1,11 +1,44 @@
+#[cfg(feature = "bn254")]
+use alloc::vec::Vec;
+use alloc::{format, string::ToString, vec};
+
+use sha2::{Digest, Sha256};
 use sp1_verifier::{Groth16Verifier, GROTH16_VK_BYTES};
-use strata_zkvm::{Proof, ZkVmError, ZkVmResult};
+#[cfg(feature = "bn254")]
+use strata_zkvm::GROTH16_COMPACT_SIZE;
+use strata_zkvm::{
+    CanonicalGroth16Proof, Groth16Proof, Proof, ZkVmError, ZkVmProofError, ZkVmResult,
+    GROTH16_POINTS_SIZE, GROTH16_SELECTOR_SIZE,
+};
+
+/// A [`strata_zkvm::Groth16Verifier`] for Groth16 proofs generated by SP1.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SP1Groth16Verifier;
+
+impl strata_zkvm::Groth16Verifier for SP1Groth16Verifier {
+    fn verify_groth16(
+        &self,
+        proof: &Proof,
+        verification_key: &[u8; 32],
+        public_values: &[u8],
+    ) -> ZkVmResult<()> {
+        verify_groth16(proof, verification_key, public_values)
+    }
+}
 
 pub fn verify_groth16(
     proof: &Proof,
     vkey_hash: &[u8; 32],
     committed_values_raw: &[u8],
 ) -> ZkVmResult<()> {
+    // `Groth16Verifier` slices the selector and the points without checking the proof length
+    let proof_len = proof.as_bytes().len();
+    if proof_len != GROTH16_SELECTOR_SIZE + GROTH16_POINTS_SIZE {
+        return Err(ZkVmError::ProofVerificationError(format!(
+            "invalid SP1 Groth16 proof length {proof_len}"
+        )));
+    }
+
     let vk_hash_str = hex::encode(vkey_hash);
     let vk_hash_str = format!("0x{}", vk_hash_str);
 
@@ -22,6 +55,103 @@ pub fn verify_groth16(
     .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))
 }
 
+/// Converts a [`Groth16Proof`] extracted from an SP1 receipt into a [`CanonicalGroth16Proof`].
+///
+/// The public inputs of the SP1 Groth16 circuit are the program commitment `vkey_hash` and the
+/// SHA-256 digest of the public values with its three most significant bits cleared.
+pub fn to_canonical_groth16_proof(
+    proof: &Groth16Proof,
+    vkey_hash: &[u8; 32],
+) -> ZkVmResult<CanonicalGroth16Proof> {
+    let seal = proof.seal().as_bytes();
+    if seal.len() != GROTH16_SELECTOR_SIZE + GROTH16_POINTS_SIZE {
+        return Err(ZkVmProofError::InvalidGroth16Encoding(format!(
+            "invalid SP1 Groth16 proof length {}",
+            seal.len()
+        ))
+        .into());
+    }
+
+    let mut committed_values_digest = *proof.public_values_digest();
+    committed_values_digest[0] &= 0b0001_1111;
+
+    CanonicalGroth16Proof::from_gnark_bytes(
+        &seal[GROTH16_SELECTOR_SIZE..],
+        vec![*vkey_hash, committed_values_digest],
+    )
+}
+
+/// Converts a [`CanonicalGroth16Proof`] back into the proof bytes expected by [`verify_groth16`].
+pub fn from_canonical_groth16_proof(proof: &CanonicalGroth16Proof) -> Proof {
+    let selector = &Sha256::digest(&*GROTH16_VK_BYTES)[..GROTH16_SELECTOR_SIZE];
+    Proof::new([selector, &proof.to_gnark_bytes()].concat())
+}
+
+/// Converts a [`Groth16Proof`] extracted from an SP1 receipt into the compact encoding of
+/// [`CanonicalGroth16Proof::to_compact_bytes`].
+#[cfg(feature = "bn254")]
+pub fn to_compact_groth16_proof(
+    proof: &Groth16Proof,
+    vkey_hash: &[u8; 32],
+) -> ZkVmResult<[u8; GROTH16_COMPACT_SIZE]> {
+    to_canonical_groth16_proof(proof, vkey_hash)?.to_compact_bytes()
+}
+
+/// Converts a compact proof back into the proof bytes expected by [`verify_groth16`].
+#[cfg(feature = "bn254")]
+pub fn from_compact_groth16_proof(bytes: &[u8]) -> ZkVmResult<Proof> {
+    let proof = CanonicalGroth16Proof::from_compact_bytes(bytes, Vec::new())?;
+    Ok(from_canonical_groth16_proof(&proof))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_proof_lengths_are_rejected() {
+        for len in [0, 10, GROTH16_SELECTOR_SIZE + GROTH16_POINTS_SIZE + 1] {
+            let proof = Proof::new(vec![1; len]);
+            let err = verify_groth16(&proof, &[0; 32], &[]).unwrap_err();
+            assert!(matches!(
+                err,
+                ZkVmError::ProofVerificationError(ref msg)
+                    if msg == &format!("invalid SP1 Groth16 proof length {len}")
+            ));
+
+            let proof = Groth16Proof::new(proof, [0; 32]);
+            assert!(matches!(
+                to_canonical_groth16_proof(&proof, &[0; 32]),
+                Err(ZkVmError::InvalidProofReceipt(
+                    ZkVmProofError::InvalidGroth16Encoding(_)
+                ))
+            ));
+        }
+    }
+
+    /// The compact encoding of a proof made of the generators of BN254.
+    #[cfg(feature = "bn254")]
+    const COMPACT_PROOF: &str = "8000000000000000000000000000000000000000000000000000000000000001998e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6edc000000000000000000000000000000000000000000000000000000000000001";
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn test_compact_roundtrip() {
+        let compact = hex::decode(COMPACT_PROOF).unwrap();
+        let seal = from_compact_groth16_proof(&compact).unwrap();
+        assert_eq!(
+            seal.as_bytes().len(),
+            GROTH16_SELECTOR_SIZE + GROTH16_POINTS_SIZE
+        );
+
+        let proof = Groth16Proof::new(seal, [0xff; 32]);
+        assert_eq!(
+            to_compact_groth16_proof(&proof, &[1; 32]).unwrap()[..],
+            compact
+        );
+        assert!(from_compact_groth16_proof(&compact[1..]).is_err());
+    }
+}
+
 // // NOTE: SP1 prover runs in release mode only; therefore run the tests on release mode only
 // #[cfg(test)]
 // mod tests {
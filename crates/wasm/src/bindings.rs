@@ -0,0 +1,42 @@
+use strata_zkvm::{Proof, ProofReceipt, ZkVm};
+use wasm_bindgen::prelude::*;
+
+fn parse_zkvm(zkvm: &str) -> Result<ZkVm, JsError> {
+    match zkvm {
+        "sp1" => Ok(ZkVm::SP1),
+        "risc0" => Ok(ZkVm::Risc0),
+        _ => Err(JsError::new(&format!("unsupported zkVM: {zkvm}"))),
+    }
+}
+
+fn parse_key(verification_key: &[u8]) -> Result<[u8; 32], JsError> {
+    verification_key
+        .try_into()
+        .map_err(|_| JsError::new("verification key must be 32 bytes"))
+}
+
+/// Verifies a borsh-encoded `ProofReceipt` produced by the `"sp1"` or `"risc0"` host.
+#[wasm_bindgen(js_name = verifyReceipt)]
+pub fn verify_receipt(zkvm: &str, receipt: &[u8], verification_key: &[u8]) -> Result<(), JsError> {
+    let receipt: ProofReceipt =
+        borsh::from_slice(receipt).map_err(|e| JsError::new(&e.to_string()))?;
+    crate::verify_receipt(parse_zkvm(zkvm)?, &receipt, &parse_key(verification_key)?)
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Verifies the seal of a Groth16 proof produced by the `"sp1"` or `"risc0"` host.
+#[wasm_bindgen(js_name = verifyGroth16)]
+pub fn verify_groth16(
+    zkvm: &str,
+    proof: &[u8],
+    verification_key: &[u8],
+    public_values: &[u8],
+) -> Result<(), JsError> {
+    crate::verify_groth16(
+        parse_zkvm(zkvm)?,
+        &Proof::from(proof),
+        &parse_key(verification_key)?,
+        public_values,
+    )
+    .map_err(|e| JsError::new(&e.to_string()))
+}
@@ -0,0 +1,78 @@
+//! Verification of proofs produced by the zkVM hosts, buildable for `wasm32-unknown-unknown`.
+//!
+//! Only the verifier side of the adapters is used, so that neither the SP1 nor the Risc0 SDK has
+//! to be compiled for wasm.
+
+#[cfg(feature = "bindings")]
+mod bindings;
+mod risc0;
+mod sp1;
+
+pub use risc0::verify_risc0_receipt;
+pub use sp1::{extract_sp1_groth16_seal, verify_sp1_receipt};
+use strata_risc0_adapter::Risc0Groth16Verifier;
+use strata_sp1_adapter::SP1Groth16Verifier;
+use strata_zkvm::{Groth16VerifierRegistry, Proof, ProofReceipt, ZkVm, ZkVmError, ZkVmResult};
+
+/// Returns a [`Groth16VerifierRegistry`] with the verifiers of every zkVM supported in wasm.
+pub fn groth16_verifiers() -> Groth16VerifierRegistry {
+    let mut registry = Groth16VerifierRegistry::new();
+    registry
+        .register(ZkVm::SP1, SP1Groth16Verifier)
+        .register(ZkVm::Risc0, Risc0Groth16Verifier);
+    registry
+}
+
+/// Verifies the seal of a Groth16 proof produced by the given [`ZkVm`].
+///
+/// The arguments have the semantics of [`strata_zkvm::Groth16Verifier`].
+pub fn verify_groth16(
+    zkvm: ZkVm,
+    proof: &Proof,
+    verification_key: &[u8; 32],
+    public_values: &[u8],
+) -> ZkVmResult<()> {
+    groth16_verifiers().verify_groth16(zkvm, proof, verification_key, public_values)
+}
+
+/// Verifies a [`ProofReceipt`] produced by the host of the given [`ZkVm`].
+///
+/// `verification_key` is the key returned by `ZkVmHost::get_groth16_verification_key` of the
+/// host that generated the receipt. SP1 receipts are only supported for Groth16 proofs, Risc0
+/// receipts are supported for succinct and Groth16 proofs.
+pub fn verify_receipt(
+    zkvm: ZkVm,
+    receipt: &ProofReceipt,
+    verification_key: &[u8; 32],
+) -> ZkVmResult<()> {
+    match zkvm {
+        ZkVm::SP1 => verify_sp1_receipt(receipt, verification_key),
+        ZkVm::Risc0 => verify_risc0_receipt(receipt, verification_key),
+        ZkVm::Native => Err(ZkVmError::UnsupportedZkVm(zkvm)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_zkvm::PublicValues;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_native_receipts_are_unsupported() {
+        let receipt = ProofReceipt::new(Proof::default(), PublicValues::default());
+        let res = verify_receipt(ZkVm::Native, &receipt, &[0; 32]);
+        assert!(matches!(res, Err(ZkVmError::UnsupportedZkVm(ZkVm::Native))));
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_invalid_groth16_seals_are_rejected() {
+        let proof = Proof::new(vec![0; 260]);
+        for zkvm in [ZkVm::SP1, ZkVm::Risc0] {
+            assert!(verify_groth16(zkvm, &proof, &[0; 32], b"public values").is_err());
+        }
+        let res = verify_groth16(ZkVm::Native, &proof, &[0; 32], b"public values");
+        assert!(matches!(res, Err(ZkVmError::UnsupportedZkVm(ZkVm::Native))));
+    }
+}
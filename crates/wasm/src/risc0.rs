@@ -0,0 +1,45 @@
+use risc0_zkvm::{sha::Digest, InnerReceipt, Receipt};
+use strata_zkvm::{deserialize_proof, ProofReceipt, ZkVmError, ZkVmResult};
+
+/// Verifies a [`ProofReceipt`] produced by `Risc0Host` against the program's image id.
+///
+/// Succinct and Groth16 receipts are supported. Fake receipts generated in dev mode are
+/// rejected.
+pub fn verify_risc0_receipt(receipt: &ProofReceipt, image_id: &[u8; 32]) -> ZkVmResult<()> {
+    let inner: InnerReceipt = deserialize_proof(receipt.proof().as_bytes())?;
+    let journal = receipt.public_values().as_bytes().to_vec();
+
+    Receipt::new(inner, journal)
+        .verify(Digest::from_bytes(*image_id))
+        .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm::{FakeReceipt, MaybePruned, ReceiptClaim};
+    use strata_zkvm::{Proof, PublicValues};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_malformed_receipt_is_rejected() {
+        let receipt = ProofReceipt::new(Proof::new(vec![0xff; 8]), PublicValues::default());
+        let res = verify_risc0_receipt(&receipt, &[0; 32]);
+        assert!(matches!(res, Err(ZkVmError::InvalidProofReceipt(_))));
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_fake_receipt_is_rejected() {
+        let journal = b"public values".to_vec();
+        let claim = ReceiptClaim::ok(Digest::ZERO, MaybePruned::from(journal.clone()));
+        let inner = InnerReceipt::Fake(FakeReceipt::new(claim));
+        let receipt = ProofReceipt::new(
+            Proof::new(bincode::serialize(&inner).unwrap()),
+            PublicValues::new(journal),
+        );
+
+        let res = verify_risc0_receipt(&receipt, &[0; 32]);
+        assert!(matches!(res, Err(ZkVmError::ProofVerificationError(_))));
+    }
+}
@@ -0,0 +1,73 @@
+use strata_sp1_adapter::{extract_groth16_seal, verify_groth16};
+use strata_zkvm::{Proof, ProofReceipt, ZkVmResult};
+
+/// Extracts the Groth16 seal expected by [`verify_groth16`] from the proof of a receipt produced
+/// by `SP1Host`.
+///
+/// This is the equivalent of `SP1ProofWithPublicValues::bytes` for Groth16 proofs.
+pub fn extract_sp1_groth16_seal(receipt: &ProofReceipt) -> ZkVmResult<Proof> {
+    extract_groth16_seal(receipt.proof())
+}
+
+/// Verifies a Groth16 [`ProofReceipt`] produced by `SP1Host` against the program's
+/// `vkey_hash`.
+pub fn verify_sp1_receipt(receipt: &ProofReceipt, vkey_hash: &[u8; 32]) -> ZkVmResult<()> {
+    let seal = extract_sp1_groth16_seal(receipt)?;
+    verify_groth16(&seal, vkey_hash, receipt.public_values().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_zkvm::{ProofType, PublicValues, ZkVmError, ZkVmProofError, GROTH16_SELECTOR_SIZE};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    fn groth16_receipt(encoded_proof: &[u8]) -> ProofReceipt {
+        // `SP1Proof::Groth16`, whose fields are encoded by bincode like a tuple
+        let groth16 = (
+            [String::new(), String::new()],
+            hex::encode(encoded_proof),
+            String::new(),
+            [7u8; 32],
+        );
+        let mut proof = 3u32.to_le_bytes().to_vec();
+        proof.extend(bincode::serialize(&groth16).unwrap());
+        ProofReceipt::new(
+            Proof::new(proof),
+            PublicValues::new(b"public values".to_vec()),
+        )
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_extract_groth16_seal() {
+        let receipt = groth16_receipt(&[1; 256]);
+        let seal = extract_sp1_groth16_seal(&receipt).unwrap();
+        assert_eq!(
+            seal.as_bytes()[..GROTH16_SELECTOR_SIZE],
+            [7; GROTH16_SELECTOR_SIZE]
+        );
+        assert_eq!(seal.as_bytes()[GROTH16_SELECTOR_SIZE..], [1; 256]);
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_non_groth16_receipts_are_rejected() {
+        // An empty `SP1Proof::Core` proof
+        let proof = [0u32.to_le_bytes(), [0; 4], [0; 4]].concat();
+        let receipt = ProofReceipt::new(Proof::new(proof), PublicValues::default());
+        let res = verify_sp1_receipt(&receipt, &[0; 32]);
+        assert!(matches!(
+            res,
+            Err(ZkVmError::InvalidProofReceipt(
+                ZkVmProofError::InvalidProofType(ProofType::Groth16)
+            ))
+        ));
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn test_invalid_groth16_receipt_fails_verification() {
+        let receipt = groth16_receipt(&[1; 256]);
+        let res = verify_sp1_receipt(&receipt, &[7; 32]);
+        assert!(matches!(res, Err(ZkVmError::ProofVerificationError(_))));
+    }
+}
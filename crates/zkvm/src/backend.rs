@@ -0,0 +1,325 @@
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{ProofReceipt, ProofType, RecordedInput, ZkVm, ZkVmError, ZkVmResult};
+
+/// The prover a host generates its proofs with.
+///
+/// `Env`, `Local` and `Mock` prove in the host's own process with the zkVM SDK.
+/// `ExternalProcess` and `Remote` delegate proving: the host sends a [`ProveRequest`] holding its
+/// ELF and the recorded input, and gets a [`ProofReceipt`] back. Execution and verification
+/// always happen locally.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProverBackend {
+    /// Lets the zkVM SDK choose the prover from the environment, e.g. `SP1_PROVER` for SP1 and
+    /// `RISC0_PROVER` or `BONSAI_API_URL` for Risc0.
+    #[default]
+    Env,
+    /// Proves on the local CPU.
+    Local,
+    /// Generates mock proofs, which are only accepted by verifiers running in mock mode.
+    Mock,
+    /// Runs `program` for every proof. The borsh-encoded [`ProveRequest`] is written to its
+    /// stdin and the borsh-encoded [`ProveResponse`] is read from its stdout. See
+    /// [`serve_stdio`].
+    ExternalProcess { program: PathBuf, args: Vec<String> },
+    /// Sends the borsh-encoded [`ProveRequest`] to `{endpoint}/prove` over HTTP, which requires
+    /// the `remote` feature. See `StandInProverServer` with the `test-utils` feature.
+    Remote { endpoint: String },
+}
+
+impl ProverBackend {
+    /// Returns `true` if proofs are generated outside the host, in which case they are obtained
+    /// with [`ProverBackend::prove_delegated`].
+    pub fn is_delegated(&self) -> bool {
+        matches!(self, Self::ExternalProcess { .. } | Self::Remote { .. })
+    }
+
+    /// Sends the request to the external process or remote endpoint of the backend and returns
+    /// the proof it generated.
+    pub fn prove_delegated(&self, request: &ProveRequest) -> ZkVmResult<ProofReceipt> {
+        let response = match self {
+            Self::ExternalProcess { program, args } => prove_external(program, args, request)?,
+            Self::Remote { endpoint } => prove_remote(endpoint, request)?,
+            _ => {
+                return Err(ZkVmError::Other(format!(
+                    "{self:?} backend does not delegate proving"
+                )))
+            }
+        };
+        response.map_err(ZkVmError::ProofGenerationError)
+    }
+}
+
+/// A request to prove a program on a delegated [`ProverBackend`].
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProveRequest {
+    /// The zkVM the proof must be generated with.
+    pub zkvm: ZkVm,
+    /// The guest program.
+    pub elf: Vec<u8>,
+    /// The input of the program, replayed into the input builder of the zkVM by the prover.
+    pub input: RecordedInput,
+    /// The type of proof to generate.
+    pub proof_type: ProofType,
+}
+
+/// The response to a [`ProveRequest`], with the error message if proving failed.
+pub type ProveResponse = Result<ProofReceipt, String>;
+
+fn prove_external(
+    program: &Path,
+    args: &[String],
+    request: &ProveRequest,
+) -> ZkVmResult<ProveResponse> {
+    let backend_error = |e: std::io::Error| {
+        ZkVmError::ProofGenerationError(format!("external prover {}: {e}", program.display()))
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(backend_error)?;
+
+    // Dropping stdin closes it, which tells the prover the whole request was written
+    let request = borsh::to_vec(request).map_err(backend_error)?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(&request)
+        .map_err(backend_error)?;
+
+    let output = child.wait_with_output().map_err(backend_error)?;
+    if !output.status.success() {
+        return Err(ZkVmError::ProofGenerationError(format!(
+            "external prover {} exited with {}",
+            program.display(),
+            output.status
+        )));
+    }
+    borsh::from_slice(&output.stdout).map_err(backend_error)
+}
+
+#[cfg(feature = "remote")]
+fn prove_remote(endpoint: &str, request: &ProveRequest) -> ZkVmResult<ProveResponse> {
+    let backend_error =
+        |e: &dyn std::fmt::Display| ZkVmError::ProofGenerationError(format!("{endpoint}: {e}"));
+
+    let request = borsh::to_vec(request).map_err(|e| backend_error(&e))?;
+    let response = ureq::post(&format!("{}/prove", endpoint.trim_end_matches('/')))
+        .set("Content-Type", "application/octet-stream")
+        .send_bytes(&request)
+        .map_err(|e| backend_error(&e))?;
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut body)
+        .map_err(|e| backend_error(&e))?;
+    borsh::from_slice(&body).map_err(|e| backend_error(&e))
+}
+
+#[cfg(not(feature = "remote"))]
+fn prove_remote(endpoint: &str, _request: &ProveRequest) -> ZkVmResult<ProveResponse> {
+    Err(ZkVmError::Other(format!(
+        "cannot prove on {endpoint}: strata-zkvm is built without the `remote` feature"
+    )))
+}
+
+/// Serves a single [`ProveRequest`] over stdin and stdout, as expected from the program of a
+/// [`ProverBackend::ExternalProcess`] backend.
+///
+/// Errors returned by `prove` are sent back to the host. An error is only returned if the request
+/// could not be read or the response could not be written.
+pub fn serve_stdio(
+    prove: impl FnOnce(ProveRequest) -> ZkVmResult<ProofReceipt>,
+) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    std::io::stdin().read_to_end(&mut request)?;
+    let request: ProveRequest = borsh::from_slice(&request)?;
+
+    let response: ProveResponse = prove(request).map_err(|e| e.to_string());
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(&borsh::to_vec(&response)?)?;
+    stdout.flush()
+}
+
+#[cfg(feature = "test-utils")]
+pub use stand_in::StandInProverServer;
+
+#[cfg(feature = "test-utils")]
+mod stand_in {
+    use std::{
+        sync::Arc,
+        thread::{self, JoinHandle},
+    };
+
+    use tiny_http::{Method, Response, Server};
+
+    use super::{ProveRequest, ProveResponse};
+    use crate::{ProofReceipt, ZkVmResult};
+
+    type ProveFn = dyn Fn(ProveRequest) -> ZkVmResult<ProofReceipt> + Send + Sync;
+
+    /// A minimal in-process server for the [`ProverBackend::Remote`] protocol.
+    ///
+    /// It answers `POST /prove` with the result of the given closure, so tests can point a host
+    /// at a remote backend without running a real proving service, e.g. by proving with a
+    /// `NativeHost` or a mock prover. The server listens on a free local port and stops when
+    /// dropped.
+    ///
+    /// [`ProverBackend::Remote`]: super::ProverBackend::Remote
+    pub struct StandInProverServer {
+        server: Arc<Server>,
+        endpoint: String,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl StandInProverServer {
+        /// Starts a server answering prove requests with `prove`.
+        pub fn start(
+            prove: impl Fn(ProveRequest) -> ZkVmResult<ProofReceipt> + Send + Sync + 'static,
+        ) -> std::io::Result<Self> {
+            let server = Server::http("127.0.0.1:0").map_err(std::io::Error::other)?;
+            let addr = server
+                .server_addr()
+                .to_ip()
+                .expect("server listens on a tcp socket");
+            let server = Arc::new(server);
+
+            let prove: Box<ProveFn> = Box::new(prove);
+            let handle = thread::spawn({
+                let server = server.clone();
+                move || {
+                    for mut request in server.incoming_requests() {
+                        let (status, body) =
+                            if request.method() != &Method::Post || request.url() != "/prove" {
+                                (404, Vec::new())
+                            } else {
+                                let mut body = Vec::new();
+                                match request.as_reader().read_to_end(&mut body) {
+                                    Ok(_) => (200, handle_prove(&*prove, &body)),
+                                    Err(_) => (400, Vec::new()),
+                                }
+                            };
+                        let _ = request.respond(Response::from_data(body).with_status_code(status));
+                    }
+                }
+            });
+
+            Ok(Self {
+                server,
+                endpoint: format!("http://{addr}"),
+                handle: Some(handle),
+            })
+        }
+
+        /// Returns the endpoint to configure [`ProverBackend::Remote`] with.
+        ///
+        /// [`ProverBackend::Remote`]: super::ProverBackend::Remote
+        pub fn endpoint(&self) -> &str {
+            &self.endpoint
+        }
+    }
+
+    fn handle_prove(prove: &ProveFn, body: &[u8]) -> Vec<u8> {
+        let response: ProveResponse = match borsh::from_slice::<ProveRequest>(body) {
+            Ok(request) => prove(request).map_err(|e| e.to_string()),
+            Err(e) => Err(format!("invalid prove request: {e}")),
+        };
+        borsh::to_vec(&response).expect("serializing to a vec does not fail")
+    }
+
+    impl Drop for StandInProverServer {
+        fn drop(&mut self) {
+            self.server.unblock();
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ProveRequest {
+        ProveRequest {
+            zkvm: ZkVm::Native,
+            elf: vec![],
+            input: RecordedInput::default(),
+            proof_type: ProofType::Core,
+        }
+    }
+
+    #[test]
+    fn test_local_backends_do_not_delegate() {
+        for backend in [
+            ProverBackend::Env,
+            ProverBackend::Local,
+            ProverBackend::Mock,
+        ] {
+            assert!(!backend.is_delegated());
+            assert!(backend.prove_delegated(&request()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_external_process_errors() {
+        let missing = ProverBackend::ExternalProcess {
+            program: PathBuf::from("/nonexistent/prover"),
+            args: vec![],
+        };
+        assert!(matches!(
+            missing.prove_delegated(&request()),
+            Err(ZkVmError::ProofGenerationError(_))
+        ));
+
+        // `cat` echoes the request, which is not a valid response
+        let echo = ProverBackend::ExternalProcess {
+            program: PathBuf::from("cat"),
+            args: vec![],
+        };
+        assert!(echo.prove_delegated(&request()).is_err());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_stand_in_server() {
+        use crate::{Proof, PublicValues};
+
+        let server = StandInProverServer::start(|request| match request.proof_type {
+            ProofType::Core => Ok(ProofReceipt::new(
+                Proof::new(request.elf),
+                PublicValues::new(vec![1, 2, 3]),
+            )),
+            _ => Err(ZkVmError::ProofGenerationError("unsupported".to_string())),
+        })
+        .unwrap();
+        let backend = ProverBackend::Remote {
+            endpoint: server.endpoint().to_string(),
+        };
+        assert!(backend.is_delegated());
+
+        let mut req = request();
+        req.elf = vec![7, 7];
+        let receipt = backend.prove_delegated(&req).unwrap();
+        assert_eq!(receipt.proof().as_bytes(), &[7, 7]);
+        assert_eq!(receipt.public_values().as_bytes(), &[1, 2, 3]);
+
+        req.proof_type = ProofType::Groth16;
+        assert!(matches!(
+            backend.prove_delegated(&req),
+            Err(ZkVmError::ProofGenerationError(msg)) if msg.contains("unsupported")
+        ));
+    }
+}
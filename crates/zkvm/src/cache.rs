@@ -0,0 +1,333 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    host::ZkVmHost, ProgramId, ProofReceipt, ProofType, RecordedInput, ZkVmError, ZkVmResult,
+};
+
+/// Counter making the names of the temporary files unique across the writes of a process.
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// The key of a proof in a [`ProofCache`].
+///
+/// A proof is only reused for the same program, the same input, the same proof type and the same
+/// version of the zkVM backend, so that upgrading the backend never returns a proof generated by
+/// the previous version.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProofCacheKey {
+    program_id: ProgramId,
+    input_hash: [u8; 32],
+    proof_type: ProofType,
+    backend_version: String,
+}
+
+impl ProofCacheKey {
+    /// Creates the key of the proof of `input` with the given host.
+    ///
+    /// Returns `None` if the host has no [`ProgramId`], like `NativeHost`, since the key would
+    /// not tell its programs apart.
+    pub fn new(host: &impl ZkVmHost, input: &RecordedInput, proof_type: ProofType) -> Option<Self> {
+        let program_id = host.program_id();
+        if program_id == ProgramId::new([0; 8]) {
+            return None;
+        }
+
+        let input = borsh::to_vec(input).expect("serializing to a vec does not fail");
+        Some(Self {
+            program_id,
+            input_hash: Sha256::digest(input).into(),
+            proof_type,
+            backend_version: host.backend_version(),
+        })
+    }
+
+    /// Returns the SHA-256 digest of the key, which addresses the proof in the cache.
+    pub fn digest(&self) -> [u8; 32] {
+        let key = borsh::to_vec(self).expect("serializing to a vec does not fail");
+        Sha256::digest(key).into()
+    }
+}
+
+/// A cache entry as stored on disk, prefixed by the SHA-256 checksum of its serialization.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct CacheEntry {
+    key: ProofCacheKey,
+    receipt: ProofReceipt,
+}
+
+/// A content-addressed, on-disk cache of proofs.
+///
+/// Each proof is stored in its own `.proof` file named after the digest of its
+/// [`ProofCacheKey`], along with the key and a checksum. Cached proofs are verified with the host
+/// before being returned, and entries that fail any check are removed and treated as misses.
+///
+/// The cache can be bounded in number of entries and in total size, in which case the least
+/// recently used entries are evicted after each insertion. Recency is tracked with the
+/// modification time of the files, which is updated on every hit.
+#[derive(Debug, Clone)]
+pub struct ProofCache {
+    dir: PathBuf,
+    max_entries: Option<usize>,
+    max_bytes: Option<u64>,
+}
+
+impl ProofCache {
+    /// Creates an unbounded cache storing its entries in `dir`. The directory is created on the
+    /// first insertion.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_entries: None,
+            max_bytes: None,
+        }
+    }
+
+    /// Limits the number of cached proofs.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Limits the total size of the cached proofs, in bytes.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Returns the directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the entry for the given key.
+    pub fn entry_path(&self, key: &ProofCacheKey) -> PathBuf {
+        self.dir
+            .join(format!("{}.proof", hex::encode(key.digest())))
+    }
+
+    /// Returns the cached proof for `key` if it passes verification with `host`.
+    pub fn get(&self, key: &ProofCacheKey, host: &impl ZkVmHost) -> Option<ProofReceipt> {
+        self.get_verified(key, |receipt| host.verify(receipt))
+    }
+
+    fn get_verified(
+        &self,
+        key: &ProofCacheKey,
+        verify: impl FnOnce(&ProofReceipt) -> ZkVmResult<()>,
+    ) -> Option<ProofReceipt> {
+        let path = self.entry_path(key);
+        let bytes = fs::read(&path).ok()?;
+        let res = decode_entry(&bytes, key).and_then(|receipt| {
+            verify(&receipt).map_err(|e| e.to_string())?;
+            Ok(receipt)
+        });
+        match res {
+            Ok(receipt) => {
+                // Mark the entry as recently used, failing to do so only affects eviction
+                let _ = File::options()
+                    .append(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()));
+                Some(receipt)
+            }
+            Err(_) => {
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Stores the proof for `key`, replacing any existing entry, and evicts the least recently
+    /// used entries if the cache exceeds its limits.
+    pub fn put(&self, key: &ProofCacheKey, receipt: &ProofReceipt) -> ZkVmResult<()> {
+        let entry = CacheEntry {
+            key: key.clone(),
+            receipt: receipt.clone(),
+        };
+        let payload = borsh::to_vec(&entry).map_err(cache_error)?;
+        let bytes = [Sha256::digest(&payload).as_slice(), &payload].concat();
+
+        // Write to a temporary file first so that concurrent readers never see a partial entry
+        fs::create_dir_all(&self.dir).map_err(cache_error)?;
+        let path = self.entry_path(key);
+        let tmp_path = path.with_extension(format!(
+            "tmp-{}-{}",
+            std::process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, bytes).map_err(cache_error)?;
+        fs::rename(&tmp_path, &path).map_err(cache_error)?;
+
+        self.evict()
+    }
+
+    /// Evicts the least recently used entries until the cache is within its limits.
+    pub fn evict(&self) -> ZkVmResult<()> {
+        if self.max_entries.is_none() && self.max_bytes.is_none() {
+            return Ok(());
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(cache_error)? {
+            let path = entry.map_err(cache_error)?.path();
+            if path.extension().is_some_and(|ext| ext == "proof") {
+                let metadata = fs::metadata(&path).map_err(cache_error)?;
+                let modified = metadata.modified().map_err(cache_error)?;
+                entries.push((modified, metadata.len(), path));
+            }
+        }
+        entries.sort();
+
+        let mut count = entries.len();
+        let mut total_bytes: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        for (_, len, path) in entries {
+            let too_many = self.max_entries.is_some_and(|max| count > max);
+            let too_large = self.max_bytes.is_some_and(|max| total_bytes > max);
+            if !too_many && !too_large {
+                break;
+            }
+            fs::remove_file(&path).map_err(cache_error)?;
+            count -= 1;
+            total_bytes -= len;
+        }
+        Ok(())
+    }
+
+    /// Removes all the cached proofs.
+    pub fn clear(&self) -> ZkVmResult<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(cache_error(err)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn decode_entry(bytes: &[u8], key: &ProofCacheKey) -> Result<ProofReceipt, String> {
+    if bytes.len() < 32 {
+        return Err("truncated entry".to_string());
+    }
+    let (checksum, payload) = bytes.split_at(32);
+    if Sha256::digest(payload).as_slice() != checksum {
+        return Err("checksum mismatch".to_string());
+    }
+
+    let entry: CacheEntry = borsh::from_slice(payload).map_err(|e| e.to_string())?;
+    if &entry.key != key {
+        return Err("key mismatch".to_string());
+    }
+    Ok(entry.receipt)
+}
+
+fn cache_error(err: impl std::fmt::Display) -> ZkVmError {
+    ZkVmError::Other(format!("proof cache: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+    use crate::{Proof, PublicValues};
+
+    fn temp_cache(name: &str) -> ProofCache {
+        let dir = std::env::temp_dir().join(format!("proof-cache-{name}-{}", std::process::id()));
+        let cache = ProofCache::new(dir);
+        cache.clear().unwrap();
+        cache
+    }
+
+    fn key(n: u8) -> ProofCacheKey {
+        ProofCacheKey {
+            program_id: ProgramId::new([1; 8]),
+            input_hash: [n; 32],
+            proof_type: ProofType::Core,
+            backend_version: "test-1.0".to_string(),
+        }
+    }
+
+    fn receipt(n: u8) -> ProofReceipt {
+        ProofReceipt::new(Proof::new(vec![n; 64]), PublicValues::new(vec![n]))
+    }
+
+    #[test]
+    fn test_get_put() {
+        let cache = temp_cache("get-put");
+        assert!(cache.get_verified(&key(1), |_| Ok(())).is_none());
+
+        cache.put(&key(1), &receipt(1)).unwrap();
+        assert_eq!(cache.get_verified(&key(1), |_| Ok(())), Some(receipt(1)));
+        assert!(cache.get_verified(&key(2), |_| Ok(())).is_none());
+
+        // Any part of the key addresses a different entry
+        let mut other_version = key(1);
+        other_version.backend_version = "test-2.0".to_string();
+        assert_ne!(other_version.digest(), key(1).digest());
+        assert!(cache.get_verified(&other_version, |_| Ok(())).is_none());
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_invalid_entries_are_removed() {
+        let cache = temp_cache("invalid");
+
+        // An entry failing verification
+        cache.put(&key(1), &receipt(1)).unwrap();
+        let res = cache.get_verified(&key(1), |_| {
+            Err(ZkVmError::ProofVerificationError("bad proof".to_string()))
+        });
+        assert!(res.is_none());
+        assert!(!cache.entry_path(&key(1)).exists());
+
+        // A corrupted entry
+        cache.put(&key(2), &receipt(2)).unwrap();
+        let path = cache.entry_path(&key(2));
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&path, bytes).unwrap();
+        assert!(cache.get_verified(&key(2), |_| Ok(())).is_none());
+        assert!(!path.exists());
+
+        // An entry stored under the digest of another key
+        cache.put(&key(3), &receipt(3)).unwrap();
+        fs::rename(cache.entry_path(&key(3)), cache.entry_path(&key(4))).unwrap();
+        assert!(cache.get_verified(&key(4), |_| Ok(())).is_none());
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_eviction() {
+        let cache = temp_cache("eviction").with_max_entries(2);
+        cache.put(&key(1), &receipt(1)).unwrap();
+        thread::sleep(Duration::from_millis(20));
+        cache.put(&key(2), &receipt(2)).unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        // Using the first entry makes the second one the least recently used
+        assert!(cache.get_verified(&key(1), |_| Ok(())).is_some());
+        thread::sleep(Duration::from_millis(20));
+        cache.put(&key(3), &receipt(3)).unwrap();
+
+        assert!(cache.entry_path(&key(1)).exists());
+        assert!(!cache.entry_path(&key(2)).exists());
+        assert!(cache.entry_path(&key(3)).exists());
+
+        // A size limit below a single entry evicts everything
+        let entry_len = fs::metadata(cache.entry_path(&key(1))).unwrap().len();
+        let cache = cache.with_max_bytes(entry_len - 1);
+        cache.evict().unwrap();
+        assert!(!cache.entry_path(&key(1)).exists());
+        assert!(!cache.entry_path(&key(3)).exists());
+
+        cache.clear().unwrap();
+    }
+}
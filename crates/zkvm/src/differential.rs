@@ -0,0 +1,223 @@
+use std::{
+    any::Any,
+    fmt,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+};
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::{DynZkVmHost, PublicValues, ZkVmHost, ZkVmInputRecorder, ZkVmProver, ZkVmResult};
+
+/// The outcome of executing a program on a host, with the output `T` decoded from the public
+/// values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionOutcome<T> {
+    /// The program committed public values, decoded into the output.
+    Committed(T),
+    /// The program committed public values that the host failed to decode.
+    InvalidOutput(String),
+    /// The execution returned an error.
+    Failed(String),
+    /// The execution panicked, which is how the native host surfaces guest panics.
+    Panicked(String),
+}
+
+impl<T: PartialEq> ExecutionOutcome<T> {
+    /// Checks if the program was aborted, by an error or a panic.
+    pub fn is_aborted(&self) -> bool {
+        matches!(self, Self::Failed(_) | Self::Panicked(_))
+    }
+
+    /// Checks if two outcomes agree.
+    ///
+    /// Aborted executions agree regardless of their message, since every backend reports a
+    /// rejected input in its own way. Undecodable public values agree with no outcome.
+    pub fn agrees_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Committed(a), Self::Committed(b)) => a == b,
+            _ => self.is_aborted() && other.is_aborted(),
+        }
+    }
+}
+
+/// Hosts that disagree on the execution of an input.
+#[derive(Debug, Clone)]
+pub struct Divergence<T> {
+    /// The `Debug` representation of the input.
+    pub input: String,
+    /// The outcome on each host, identified by its `Display` representation, starting with the
+    /// reference host.
+    pub outcomes: Vec<(String, ExecutionOutcome<T>)>,
+}
+
+impl<T: fmt::Debug> fmt::Display for Divergence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hosts diverge on input {}", self.input)?;
+        for (host, outcome) in &self.outcomes {
+            write!(f, "\n  {host}: {outcome:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Divergence<T> {}
+
+/// A host of a [`DifferentialHarness`], along with the decoding of its public values.
+struct HarnessHost<P: ZkVmProver> {
+    host: Box<dyn DynZkVmHost>,
+    process_output: fn(&PublicValues) -> ZkVmResult<P::Output>,
+}
+
+impl<P: ZkVmProver> HarnessHost<P> {
+    fn new<H: ZkVmHost>(host: H) -> Self {
+        Self {
+            host: Box::new(host),
+            process_output: P::process_output::<H>,
+        }
+    }
+}
+
+/// Runs the same inputs of a [`ZkVmProver`] on several hosts and compares the outcomes.
+///
+/// Guest functions run natively with `NativeHost` and compiled for the zkVMs, where the target
+/// differs from the host machine, e.g. `usize` is 32 bits wide on rv32 guests. The harness
+/// executes each input on a reference host, typically the native one, and on every other host in
+/// execute-only mode, and reports a [`Divergence`] when a host commits a different output or
+/// aborts while another does not.
+///
+/// The public values of each host are decoded with [`ZkVmProver::process_output`] for that host
+/// before they are compared, since the backends do not encode committed values the same way,
+/// e.g. `Risc0ZkVmEnv::commit_serde` uses the word-aligned serde of Risc0 where the other hosts
+/// use bincode.
+///
+/// Inputs are generated with [`Arbitrary`], either by a fuzzer through
+/// [`DifferentialHarness::check_bytes`] or from a seed with [`DifferentialHarness::fuzz`].
+pub struct DifferentialHarness<P: ZkVmProver> {
+    hosts: Vec<HarnessHost<P>>,
+    _prover: PhantomData<fn() -> P>,
+}
+
+impl<P: ZkVmProver> DifferentialHarness<P>
+where
+    P::Input: fmt::Debug + for<'b> Arbitrary<'b>,
+    P::Output: fmt::Debug + PartialEq,
+{
+    /// Creates a harness comparing the other hosts with `reference`.
+    pub fn new(reference: impl ZkVmHost) -> Self {
+        Self {
+            hosts: vec![HarnessHost::new(reference)],
+            _prover: PhantomData,
+        }
+    }
+
+    /// Adds a host compared with the reference host.
+    pub fn host(mut self, host: impl ZkVmHost) -> Self {
+        self.hosts.push(HarnessHost::new(host));
+        self
+    }
+
+    /// Executes `input` on every host and returns the outcomes, in the order of the hosts.
+    ///
+    /// Inputs rejected by [`ZkVmProver::prepare_input`] never reach the hosts and have no
+    /// outcomes.
+    pub fn check(
+        &self,
+        input: &P::Input,
+    ) -> Result<Vec<ExecutionOutcome<P::Output>>, Divergence<P::Output>> {
+        let Ok(zkvm_input) = P::prepare_input::<ZkVmInputRecorder>(input) else {
+            return Ok(Vec::new());
+        };
+
+        let outcomes: Vec<_> = self
+            .hosts
+            .iter()
+            .map(
+                |HarnessHost {
+                     host,
+                     process_output,
+                 }| {
+                    match panic::catch_unwind(AssertUnwindSafe(|| {
+                        host.execute_recorded(&zkvm_input)
+                    })) {
+                        Ok(Ok(public_values)) => match process_output(&public_values) {
+                            Ok(output) => ExecutionOutcome::Committed(output),
+                            Err(err) => ExecutionOutcome::InvalidOutput(err.to_string()),
+                        },
+                        Ok(Err(err)) => ExecutionOutcome::Failed(err.to_string()),
+                        Err(payload) => ExecutionOutcome::Panicked(panic_message(payload.as_ref())),
+                    }
+                },
+            )
+            .collect();
+
+        if outcomes
+            .iter()
+            .all(|outcome| outcome.agrees_with(&outcomes[0]))
+        {
+            return Ok(outcomes);
+        }
+        Err(Divergence {
+            input: format!("{input:?}"),
+            outcomes: self
+                .hosts
+                .iter()
+                .map(|HarnessHost { host, .. }| host.to_string())
+                .zip(outcomes)
+                .collect(),
+        })
+    }
+
+    /// Generates an input from the raw bytes of a fuzzer and checks it.
+    pub fn check_bytes(
+        &self,
+        data: &[u8],
+    ) -> Result<Vec<ExecutionOutcome<P::Output>>, Divergence<P::Output>> {
+        let input = match P::Input::arbitrary_take_rest(Unstructured::new(data)) {
+            Ok(input) => input,
+            Err(_) => return Ok(Vec::new()),
+        };
+        self.check(&input)
+    }
+
+    /// Checks `iterations` inputs generated from `seed` and returns the first divergence.
+    ///
+    /// The inputs are deterministic for a given seed, so that a divergence found e.g. in CI can
+    /// be reproduced locally.
+    pub fn fuzz(&self, iterations: usize, seed: u64) -> Result<(), Divergence<P::Output>> {
+        let mut rng = SplitMix64(seed);
+        let mut data = vec![0u8; MAX_INPUT_SIZE];
+        for _ in 0..iterations {
+            let len = (rng.next() as usize) % MAX_INPUT_SIZE;
+            data[..len]
+                .iter_mut()
+                .for_each(|byte| *byte = rng.next() as u8);
+            self.check_bytes(&data[..len])?;
+        }
+        Ok(())
+    }
+}
+
+/// The maximum number of bytes an input is generated from by [`DifferentialHarness::fuzz`].
+const MAX_INPUT_SIZE: usize = 256;
+
+/// A minimal deterministic generator for [`DifferentialHarness::fuzz`].
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "host panicked".to_string())
+}
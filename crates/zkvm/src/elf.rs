@@ -0,0 +1,163 @@
+use crate::ZkVmElfError;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+const ELF32_HEADER_SIZE: usize = 52;
+const ELF32_PROGRAM_HEADER_SIZE: usize = 32;
+const ELFCLASS32: u8 = 1;
+const ELFDATA2LSB: u8 = 1;
+const ET_EXEC: u16 = 2;
+const EM_RISCV: u16 = 243;
+const PT_LOAD: u32 = 1;
+const PF_X: u32 = 1;
+
+/// Checks that `elf` is a 32-bit little-endian RISC-V executable of at most `max_size` bytes whose
+/// entrypoint lies in an executable segment.
+///
+/// This only validates the ELF header and program headers, which catches the common mistakes of
+/// passing a host binary, a truncated file or a program built for the wrong target. The zkVM
+/// backends may still reject an ELF that passes these checks.
+pub fn validate_elf(elf: &[u8], max_size: usize) -> Result<(), ZkVmElfError> {
+    if elf.len() > max_size {
+        return Err(ZkVmElfError::TooLarge {
+            size: elf.len(),
+            max_size,
+        });
+    }
+    if elf.len() < ELF32_HEADER_SIZE || &elf[..4] != ELF_MAGIC {
+        return Err(ZkVmElfError::NotElf);
+    }
+    if elf[4] != ELFCLASS32 {
+        return Err(ZkVmElfError::NotRiscV("not a 32-bit ELF"));
+    }
+    if elf[5] != ELFDATA2LSB {
+        return Err(ZkVmElfError::NotRiscV("not a little-endian ELF"));
+    }
+    if read_u16(elf, 18) != Some(EM_RISCV) {
+        return Err(ZkVmElfError::NotRiscV("machine is not RISC-V"));
+    }
+    if read_u16(elf, 16) != Some(ET_EXEC) {
+        return Err(ZkVmElfError::NotRiscV("not an executable"));
+    }
+
+    let entry = read_u32(elf, 24).ok_or(ZkVmElfError::NotElf)?;
+    if entry % 4 != 0 {
+        return Err(ZkVmElfError::InvalidEntrypoint(entry, "not word aligned"));
+    }
+
+    let phoff = read_u32(elf, 28).ok_or(ZkVmElfError::NotElf)? as usize;
+    let phentsize = read_u16(elf, 42).ok_or(ZkVmElfError::NotElf)? as usize;
+    let phnum = read_u16(elf, 44).ok_or(ZkVmElfError::NotElf)? as usize;
+    if phentsize < ELF32_PROGRAM_HEADER_SIZE {
+        return Err(ZkVmElfError::NotElf);
+    }
+
+    for i in 0..phnum {
+        let header = i
+            .checked_mul(phentsize)
+            .and_then(|offset| phoff.checked_add(offset))
+            .and_then(|start| Some(start..start.checked_add(ELF32_PROGRAM_HEADER_SIZE)?))
+            .and_then(|range| elf.get(range))
+            .ok_or(ZkVmElfError::NotElf)?;
+        let p_type = read_u32(header, 0).expect("program header is complete");
+        let p_vaddr = read_u32(header, 8).expect("program header is complete");
+        let p_memsz = read_u32(header, 20).expect("program header is complete");
+        let p_flags = read_u32(header, 24).expect("program header is complete");
+
+        let in_segment = entry >= p_vaddr && (entry - p_vaddr) < p_memsz;
+        if p_type == PT_LOAD && p_flags & PF_X != 0 && in_segment {
+            return Ok(());
+        }
+    }
+
+    Err(ZkVmElfError::InvalidEntrypoint(
+        entry,
+        "not in an executable segment",
+    ))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_SIZE: usize = 1 << 20;
+
+    /// Builds a minimal RISC-V executable with a single loadable segment.
+    fn riscv_elf(entry: u32, flags: u32) -> Vec<u8> {
+        let mut elf = vec![0u8; ELF32_HEADER_SIZE + ELF32_PROGRAM_HEADER_SIZE];
+        elf[..4].copy_from_slice(ELF_MAGIC);
+        elf[4] = ELFCLASS32;
+        elf[5] = ELFDATA2LSB;
+        elf[16..18].copy_from_slice(&ET_EXEC.to_le_bytes());
+        elf[18..20].copy_from_slice(&EM_RISCV.to_le_bytes());
+        elf[24..28].copy_from_slice(&entry.to_le_bytes());
+        elf[28..32].copy_from_slice(&(ELF32_HEADER_SIZE as u32).to_le_bytes());
+        elf[42..44].copy_from_slice(&(ELF32_PROGRAM_HEADER_SIZE as u16).to_le_bytes());
+        elf[44..46].copy_from_slice(&1u16.to_le_bytes());
+
+        let header = &mut elf[ELF32_HEADER_SIZE..];
+        header[..4].copy_from_slice(&PT_LOAD.to_le_bytes());
+        header[8..12].copy_from_slice(&0x1000u32.to_le_bytes());
+        header[20..24].copy_from_slice(&0x1000u32.to_le_bytes());
+        header[24..28].copy_from_slice(&flags.to_le_bytes());
+        elf
+    }
+
+    #[test]
+    fn test_valid_elf() {
+        assert!(validate_elf(&riscv_elf(0x1000, PF_X), MAX_SIZE).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_elfs() {
+        let elf = riscv_elf(0x1000, PF_X);
+        assert!(matches!(
+            validate_elf(&elf, 16),
+            Err(ZkVmElfError::TooLarge { max_size: 16, .. })
+        ));
+        assert!(matches!(
+            validate_elf(b"not an elf", MAX_SIZE),
+            Err(ZkVmElfError::NotElf)
+        ));
+
+        let mut x86 = elf.clone();
+        x86[18..20].copy_from_slice(&62u16.to_le_bytes());
+        assert!(matches!(
+            validate_elf(&x86, MAX_SIZE),
+            Err(ZkVmElfError::NotRiscV(_))
+        ));
+
+        let mut truncated = elf.clone();
+        truncated.truncate(ELF32_HEADER_SIZE + 8);
+        assert!(matches!(
+            validate_elf(&truncated, MAX_SIZE),
+            Err(ZkVmElfError::NotElf)
+        ));
+
+        let mut out_of_bounds = elf.clone();
+        out_of_bounds[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            validate_elf(&out_of_bounds, MAX_SIZE),
+            Err(ZkVmElfError::NotElf)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_entrypoints() {
+        for (entry, flags) in [(0x1002, PF_X), (0x3000, PF_X), (0x1000, 0)] {
+            assert!(matches!(
+                validate_elf(&riscv_elf(entry, flags), MAX_SIZE),
+                Err(ZkVmElfError::InvalidEntrypoint(e, _)) if e == entry
+            ));
+        }
+    }
+}
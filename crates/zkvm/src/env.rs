@@ -1,7 +1,9 @@
+use alloc::vec::Vec;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::Proof;
+use crate::{ProgramId, Proof};
 
 /// A trait representing a Zero-Knowledge Virtual Machine (ZkVM) interface.
 /// Provides methods for reading inputs, committing outputs, and verifying proofs
@@ -48,9 +50,12 @@ pub trait ZkVmEnv {
 
     /// Verifies a proof generated with the ZkVM.
     ///
-    /// This method checks the validity of the proof against the provided verification key digest
-    /// and public values. It will panic if the proof fails to verify.
-    fn verify_native_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]);
+    /// This method checks the validity of the proof against the provided [`ProgramId`] and public
+    /// values. It will panic if the proof fails to verify.
+    ///
+    /// Implementations must bind the proof to `program_id`; the program being verified is chosen
+    /// by the guest, never by data read from the input.
+    fn verify_native_proof(&self, program_id: &ProgramId, public_values: &[u8]);
 
     /// Verifies a Groth16 proof.
     ///
@@ -73,9 +78,9 @@ pub trait ZkVmEnv {
     /// This is equivalent to calling [`ZkVmEnv::read_buf`] and [`ZkVmEnv::verify_native_proof`],
     /// but avoids double serialization and deserialization. The function will panic if the
     /// proof fails to verify.
-    fn read_verified_buf(&self, vk_digest: &[u32; 8]) -> Vec<u8> {
+    fn read_verified_buf(&self, program_id: &ProgramId) -> Vec<u8> {
         let public_values_raw = self.read_buf();
-        self.verify_native_proof(vk_digest, &public_values_raw);
+        self.verify_native_proof(program_id, &public_values_raw);
         public_values_raw
     }
 
@@ -84,26 +89,25 @@ pub trait ZkVmEnv {
     ///
     /// This function is meant to read the committed output of another guest function
     /// that was written with [`ZkVmEnv::commit_serde`].
-    /// It then verifies the proof against the given verification key digest.
+    /// It then verifies the proof against the given [`ProgramId`].
     ///
     /// This is equivalent to calling [`ZkVmEnv::read_serde`] and [`ZkVmEnv::verify_native_proof`],
     /// but avoids double serialization and deserialization. The function will panic if the
     /// proof fails to verify.
-    fn read_verified_serde<T: DeserializeOwned>(&self, vk_digest: &[u32; 8]) -> T;
+    fn read_verified_serde<T: DeserializeOwned>(&self, program_id: &ProgramId) -> T;
 
     /// Reads and verifies a committed output from another guest function, deserializing it using
     /// Borsh.
     ///
     /// This function is similar to [`ZkVmEnv::read_verified_serde`], but is intended for guest
     /// commitments committed via [`ZkVmEnv::commit_borsh`]. The output is expected to be
-    /// Borsh-serializable. It then verifies the proof using the internal verification key
-    /// context.
+    /// Borsh-serializable. It then verifies the proof against the given [`ProgramId`].
     ///
     /// This is equivalent to calling [`ZkVmEnv::read_borsh`] and [`ZkVmEnv::verify_native_proof`],
     /// but avoids double serialization and deserialization. The function will panic if the
     /// proof fails to verify.
-    fn read_verified_borsh<T: BorshDeserialize>(&self, vk_digest: &[u32; 8]) -> T {
-        let verified_public_values_buf = self.read_verified_buf(vk_digest);
+    fn read_verified_borsh<T: BorshDeserialize>(&self, program_id: &ProgramId) -> T {
+        let verified_public_values_buf = self.read_verified_buf(program_id);
         borsh::from_slice(&verified_public_values_buf).expect("failed borsh deserialization")
     }
 }
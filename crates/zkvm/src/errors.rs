@@ -1,6 +1,8 @@
+use alloc::string::String;
+
 use thiserror::Error;
 
-use crate::{ProofType, ZkVm};
+use crate::{ProgramId, ProofType, ZkVm};
 
 /// A convenient alias for results in the ZkVM.
 pub type ZkVmResult<T> = Result<T, ZkVmError>;
@@ -10,6 +12,9 @@ pub enum ZkVmError {
     #[error("Proof generation failed: {0}")]
     ProofGenerationError(String),
 
+    #[error("Execution failed: {0}")]
+    ExecutionError(String),
+
     #[error("Proof verification failed: {0}")]
     ProofVerificationError(String),
 
@@ -17,11 +22,14 @@ pub enum ZkVmError {
     InvalidInput(#[from] ZkVmInputError),
 
     #[error("ELF validation failed: {0}")]
-    InvalidELF(String),
+    InvalidELF(#[from] ZkVmElfError),
 
     #[error("Invalid Verification Key")]
     InvalidVerificationKey(#[from] ZkVmVerificationKeyError),
 
+    #[error("Unsupported zkVM: {0:?}")]
+    UnsupportedZkVm(ZkVm),
+
     #[error("Invalid proof receipt")]
     InvalidProofReceipt(#[from] ZkVmProofError),
 
@@ -37,16 +45,18 @@ pub enum ZkVmError {
 
 #[derive(Debug, Error)]
 pub enum DataFormatError {
+    #[cfg(feature = "std")]
     #[error("{source}")]
     Bincode {
         #[source]
         source: bincode::Error,
     },
 
-    #[error("{source}")]
+    #[error("{error}")]
     Borsh {
-        #[source]
-        source: borsh::io::Error,
+        // `borsh::io::Error` only implements `Error` when borsh is built with `std`.
+        #[cfg_attr(feature = "std", source)]
+        error: borsh::io::Error,
     },
 
     #[error("{0}")]
@@ -67,10 +77,31 @@ pub enum ZkVmInputError {
     #[error("Input verification key error")]
     VerificationKey(#[source] ZkVmVerificationKeyError),
 
+    #[error("Input proof verification failed: {0}")]
+    ProofVerification(String),
+
     #[error("Input build error: {0}")]
     InputBuild(String),
 }
 
+#[derive(Debug, Error)]
+pub enum ZkVmElfError {
+    #[error("ELF is too large: {size} bytes, maximum is {max_size} bytes")]
+    TooLarge { size: usize, max_size: usize },
+
+    #[error("not an ELF file")]
+    NotElf,
+
+    #[error("not a RISC-V executable: {0}")]
+    NotRiscV(&'static str),
+
+    #[error("invalid entrypoint {0:#x}: {1}")]
+    InvalidEntrypoint(u32, &'static str),
+
+    #[error("{0}")]
+    Other(String),
+}
+
 #[derive(Debug, Error)]
 pub enum ZkVmVerificationKeyError {
     #[error("Verification Key format error")]
@@ -78,6 +109,9 @@ pub enum ZkVmVerificationKeyError {
 
     #[error("Verification Key size error")]
     InvalidVerificationKeySize,
+
+    #[error("Verification Key does not match the program id {0}")]
+    ProgramIdMismatch(ProgramId),
 }
 
 #[derive(Debug, Error)]
@@ -90,6 +124,9 @@ pub enum ZkVmProofError {
 
     #[error("Invalid ZkVm: expected {0:?}, found {1:?}")]
     InvalidZkVm(ZkVm, ZkVm),
+
+    #[error("Invalid Groth16 proof encoding: {0}")]
+    InvalidGroth16Encoding(String),
 }
 
 #[derive(Debug, Error)]
@@ -99,6 +136,7 @@ pub enum InvalidVerificationKeySource {
 }
 
 /// Implement automatic conversion for `bincode::Error` to `DataFormatError`
+#[cfg(feature = "std")]
 impl From<bincode::Error> for DataFormatError {
     fn from(err: bincode::Error) -> Self {
         DataFormatError::Bincode { source: err }
@@ -108,11 +146,12 @@ impl From<bincode::Error> for DataFormatError {
 /// Implement automatic conversion for `borsh::io::Error` to `DataFormatError`
 impl From<borsh::io::Error> for DataFormatError {
     fn from(err: borsh::io::Error) -> Self {
-        DataFormatError::Borsh { source: err }
+        DataFormatError::Borsh { error: err }
     }
 }
 
 /// Implement automatic conversion for `bincode::Error` to `InvalidProofReceipt`
+#[cfg(feature = "std")]
 impl From<bincode::Error> for ZkVmProofError {
     fn from(err: bincode::Error) -> Self {
         let source = DataFormatError::Bincode { source: err };
@@ -123,12 +162,13 @@ impl From<bincode::Error> for ZkVmProofError {
 /// Implement automatic conversion for `borsh::io::Error` to `InvalidProofReceiptSource`
 impl From<borsh::io::Error> for ZkVmProofError {
     fn from(err: borsh::io::Error) -> Self {
-        let source = DataFormatError::Borsh { source: err };
+        let source = DataFormatError::Borsh { error: err };
         ZkVmProofError::DataFormat(source)
     }
 }
 
 /// Implement automatic conversion for `bincode::Error` to `ZkVmInputError`
+#[cfg(feature = "std")]
 impl From<bincode::Error> for ZkVmInputError {
     fn from(err: bincode::Error) -> Self {
         let source = DataFormatError::Bincode { source: err };
@@ -139,7 +179,7 @@ impl From<bincode::Error> for ZkVmInputError {
 /// Implement automatic conversion for `borsh::io::Error` to `ZkVmInputError`
 impl From<borsh::io::Error> for ZkVmInputError {
     fn from(err: borsh::io::Error) -> Self {
-        let source = DataFormatError::Borsh { source: err };
+        let source = DataFormatError::Borsh { error: err };
         ZkVmInputError::DataFormat(source)
     }
 }
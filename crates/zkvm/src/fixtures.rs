@@ -0,0 +1,219 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    host::ZkVmHost, ProgramId, ProofReceipt, ProofType, VerificationKey, ZkVmError, ZkVmResult,
+};
+
+/// The environment variable that makes [`ProofFixtures`] regenerate the fixtures.
+pub const UPDATE_FIXTURES_ENV: &str = "UPDATE_FIXTURES";
+
+/// The keys of a program, checked in next to its golden proofs.
+///
+/// Comparing the snapshot with the keys computed by a host detects any change of the keys, e.g.
+/// after a change of the guest ELF or an upgrade of the zkVM backend.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct VerificationKeySnapshot {
+    pub verification_key: VerificationKey,
+    pub program_id: ProgramId,
+    pub groth16_verification_key: [u8; 32],
+}
+
+impl VerificationKeySnapshot {
+    /// Returns the snapshot of the keys of the program loaded in `host`.
+    pub fn of(host: &impl ZkVmHost) -> Self {
+        Self {
+            verification_key: host.get_verification_key(),
+            program_id: host.program_id(),
+            groth16_verification_key: host.get_groth16_verification_key(),
+        }
+    }
+}
+
+/// A directory of golden proofs and verification key snapshots of a test program.
+///
+/// The fixtures are checked in, so that tests verify proofs of every [`ProofType`] without
+/// generating them, which needs the real provers and takes minutes for Groth16 proofs. Missing
+/// fixtures are an error, unless [`UPDATE_FIXTURES_ENV`] is set, in which case the fixtures are
+/// regenerated and saved. This is needed after any change detected by the snapshot, e.g.:
+///
+/// ```sh
+/// UPDATE_FIXTURES=1 cargo test --release -p strata-sp1-adapter
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProofFixtures {
+    dir: PathBuf,
+    update: bool,
+}
+
+impl ProofFixtures {
+    /// Creates the fixtures stored in `dir`, regenerated if [`UPDATE_FIXTURES_ENV`] is set.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            update: std::env::var_os(UPDATE_FIXTURES_ENV).is_some(),
+        }
+    }
+
+    /// Sets whether the fixtures are regenerated, regardless of [`UPDATE_FIXTURES_ENV`].
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Returns the directory of the fixtures.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the golden proof of the given type.
+    pub fn receipt_path(&self, proof_type: ProofType) -> PathBuf {
+        let name = match proof_type {
+            ProofType::Core => "core",
+            ProofType::Compressed => "compressed",
+            ProofType::Groth16 => "groth16",
+        };
+        self.dir.join(format!("proof-{name}.bin"))
+    }
+
+    /// Returns the path of the verification key snapshot.
+    pub fn vk_snapshot_path(&self) -> PathBuf {
+        self.dir.join("vk.bin")
+    }
+
+    /// Loads the golden proof of the given type.
+    pub fn load_receipt(&self, proof_type: ProofType) -> ZkVmResult<ProofReceipt> {
+        self.load(&self.receipt_path(proof_type))
+    }
+
+    /// Saves the golden proof of the given type.
+    pub fn save_receipt(&self, proof_type: ProofType, receipt: &ProofReceipt) -> ZkVmResult<()> {
+        self.save(&self.receipt_path(proof_type), receipt)
+    }
+
+    /// Loads the verification key snapshot.
+    pub fn load_vk_snapshot(&self) -> ZkVmResult<VerificationKeySnapshot> {
+        self.load(&self.vk_snapshot_path())
+    }
+
+    /// Saves the verification key snapshot.
+    pub fn save_vk_snapshot(&self, snapshot: &VerificationKeySnapshot) -> ZkVmResult<()> {
+        self.save(&self.vk_snapshot_path(), snapshot)
+    }
+
+    /// Returns the golden proof of the given type.
+    ///
+    /// When the fixtures are updated, the proof is generated with `prove` and saved instead.
+    pub fn receipt(
+        &self,
+        proof_type: ProofType,
+        prove: impl FnOnce() -> ZkVmResult<ProofReceipt>,
+    ) -> ZkVmResult<ProofReceipt> {
+        if !self.update {
+            return self.load_receipt(proof_type);
+        }
+        let receipt = prove()?;
+        self.save_receipt(proof_type, &receipt)?;
+        Ok(receipt)
+    }
+
+    /// Checks the keys of the program loaded in `host` against the snapshot and returns it.
+    ///
+    /// When the fixtures are updated, the snapshot is taken from `host` and saved instead.
+    pub fn check_vk_snapshot(&self, host: &impl ZkVmHost) -> ZkVmResult<VerificationKeySnapshot> {
+        let snapshot = VerificationKeySnapshot::of(host);
+        if self.update {
+            self.save_vk_snapshot(&snapshot)?;
+            return Ok(snapshot);
+        }
+
+        let expected = self.load_vk_snapshot()?;
+        if expected != snapshot {
+            return Err(ZkVmError::Other(format!(
+                "the keys of {host} differ from the snapshot in {}, regenerate the fixtures with \
+                 {UPDATE_FIXTURES_ENV}=1",
+                self.dir.display()
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    fn load<T: BorshDeserialize>(&self, path: &Path) -> ZkVmResult<T> {
+        let bytes = fs::read(path).map_err(|e| {
+            fixture_error(
+                path,
+                format!("{e}, generate the fixtures with {UPDATE_FIXTURES_ENV}=1"),
+            )
+        })?;
+        borsh::from_slice(&bytes).map_err(|e| fixture_error(path, e))
+    }
+
+    fn save<T: BorshSerialize>(&self, path: &Path, value: &T) -> ZkVmResult<()> {
+        let bytes = borsh::to_vec(value).map_err(|e| fixture_error(path, e))?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(path, bytes))
+            .map_err(|e| fixture_error(path, e))
+    }
+}
+
+fn fixture_error(path: &Path, err: impl std::fmt::Display) -> ZkVmError {
+    ZkVmError::Other(format!("fixture {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Proof, PublicValues};
+
+    fn temp_fixtures(name: &str) -> ProofFixtures {
+        let dir =
+            std::env::temp_dir().join(format!("proof-fixtures-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        ProofFixtures::new(dir).with_update(false)
+    }
+
+    #[test]
+    fn test_receipts() {
+        let fixtures = temp_fixtures("receipts");
+        let receipt = ProofReceipt::new(Proof::new(vec![1; 64]), PublicValues::new(vec![2; 4]));
+
+        // Missing fixtures are an error unless updating
+        assert!(fixtures
+            .receipt(ProofType::Core, || Ok(receipt.clone()))
+            .is_err());
+
+        let updating = fixtures.clone().with_update(true);
+        let saved = updating
+            .receipt(ProofType::Core, || Ok(receipt.clone()))
+            .unwrap();
+        assert_eq!(saved, receipt);
+
+        // The golden proof is loaded without proving
+        let loaded = fixtures
+            .receipt(ProofType::Core, || unreachable!())
+            .unwrap();
+        assert_eq!(loaded, receipt);
+        assert!(fixtures.load_receipt(ProofType::Groth16).is_err());
+
+        fs::remove_dir_all(fixtures.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_distinct_paths() {
+        let fixtures = temp_fixtures("paths");
+        let paths = [
+            fixtures.receipt_path(ProofType::Core),
+            fixtures.receipt_path(ProofType::Compressed),
+            fixtures.receipt_path(ProofType::Groth16),
+            fixtures.vk_snapshot_path(),
+        ];
+        for (i, path) in paths.iter().enumerate() {
+            assert!(path.starts_with(fixtures.dir()));
+            assert!(!paths[i + 1..].contains(path));
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use alloc::{format, string::String, vec::Vec};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{ZkVmProofError, ZkVmResult};
+
+/// Size in bytes of a BN254 base or scalar field element.
+const FIELD_SIZE: usize = 32;
+
+/// Size in bytes of the uncompressed `A`, `B` and `C` points.
+pub const GROTH16_POINTS_SIZE: usize = 8 * FIELD_SIZE;
+
+/// Size in bytes of the Groth16 verification key selector that prefixes SP1 Groth16 seals.
+pub const GROTH16_SELECTOR_SIZE: usize = 4;
+
+/// Size in bytes of the compressed `A`, `B` and `C` points, see
+/// [`CanonicalGroth16Proof::to_compact_bytes`].
+pub const GROTH16_COMPACT_SIZE: usize = 4 * FIELD_SIZE;
+
+/// A point on the BN254 G1 curve in affine coordinates.
+///
+/// Coordinates are big-endian encoded base field elements.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    Default,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct G1Point {
+    pub x: [u8; 32],
+    pub y: [u8; 32],
+}
+
+/// A point on the BN254 G2 curve in affine coordinates.
+///
+/// Each coordinate is an element `c0 + c1 * u` of the quadratic extension field, stored as
+/// `[c0, c1]` with big-endian encoded components. Note that the EVM and gnark encodings store the
+/// components in the reverse order, `c1` first.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    Default,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct G2Point {
+    pub x: [[u8; 32]; 2],
+    pub y: [[u8; 32]; 2],
+}
+
+/// A Groth16 proof over BN254 in a backend-independent form.
+///
+/// Consists of the proof points `A`, `B` and `C` and the public inputs of the Groth16 circuit, as
+/// big-endian encoded scalar field elements. Adapters convert the [`Groth16Proof`] of their
+/// backend to and from this form.
+///
+/// [`Groth16Proof`]: crate::Groth16Proof
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CanonicalGroth16Proof {
+    a: G1Point,
+    b: G2Point,
+    c: G1Point,
+    public_inputs: Vec<[u8; 32]>,
+}
+
+impl CanonicalGroth16Proof {
+    /// Creates a new `CanonicalGroth16Proof` from its points and public inputs.
+    pub fn new(a: G1Point, b: G2Point, c: G1Point, public_inputs: Vec<[u8; 32]>) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            public_inputs,
+        }
+    }
+
+    /// Returns the `A` point.
+    pub fn a(&self) -> &G1Point {
+        &self.a
+    }
+
+    /// Returns the `B` point.
+    pub fn b(&self) -> &G2Point {
+        &self.b
+    }
+
+    /// Returns the `C` point.
+    pub fn c(&self) -> &G1Point {
+        &self.c
+    }
+
+    /// Returns the public inputs of the Groth16 circuit.
+    pub fn public_inputs(&self) -> &[[u8; 32]] {
+        &self.public_inputs
+    }
+
+    /// Returns the uncompressed points as used by gnark, i.e. `A || B || C` with G2 coordinates
+    /// encoded as `c1 || c0`.
+    ///
+    /// This is the raw proof serialization of gnark for circuits without commitments, and the
+    /// proof layout expected by the SP1 and Risc0 Groth16 verifiers. It does not include the
+    /// public inputs.
+    pub fn to_gnark_bytes(&self) -> [u8; GROTH16_POINTS_SIZE] {
+        let mut out = [0u8; GROTH16_POINTS_SIZE];
+        for (chunk, word) in out.chunks_exact_mut(FIELD_SIZE).zip(self.point_words()) {
+            chunk.copy_from_slice(word);
+        }
+        out
+    }
+
+    /// Decodes the points from [`to_gnark_bytes`](Self::to_gnark_bytes) encoding.
+    pub fn from_gnark_bytes(bytes: &[u8], public_inputs: Vec<[u8; 32]>) -> ZkVmResult<Self> {
+        if bytes.len() != GROTH16_POINTS_SIZE {
+            return Err(encoding_error(format!(
+                "expected {} bytes, found {}",
+                GROTH16_POINTS_SIZE,
+                bytes.len()
+            )));
+        }
+        let words: Vec<[u8; 32]> = bytes.chunks_exact(FIELD_SIZE).map(to_word).collect();
+        Ok(Self::from_point_words(&words, public_inputs))
+    }
+
+    /// Returns the ABI encoded arguments of
+    /// `verifyProof(uint256[2] a, uint256[2][2] b, uint256[2] c, uint256[N] input)`, the interface
+    /// of the Solidity verifiers exported by gnark and snarkjs.
+    ///
+    /// Static arrays are encoded in place, so the calldata is the gnark encoding of the points
+    /// followed by one word per public input.
+    pub fn to_solidity_calldata(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(GROTH16_POINTS_SIZE + self.public_inputs.len() * 32);
+        out.extend_from_slice(&self.to_gnark_bytes());
+        for input in &self.public_inputs {
+            out.extend_from_slice(input);
+        }
+        out
+    }
+
+    /// Decodes a proof from [`to_solidity_calldata`](Self::to_solidity_calldata) encoding.
+    pub fn from_solidity_calldata(calldata: &[u8]) -> ZkVmResult<Self> {
+        let trailing = calldata.chunks_exact(FIELD_SIZE).remainder();
+        if calldata.len() < GROTH16_POINTS_SIZE || !trailing.is_empty() {
+            return Err(encoding_error(format!(
+                "invalid calldata length {}",
+                calldata.len()
+            )));
+        }
+        let (points, inputs) = calldata.split_at(GROTH16_POINTS_SIZE);
+        let public_inputs = inputs.chunks_exact(FIELD_SIZE).map(to_word).collect();
+        Self::from_gnark_bytes(points, public_inputs)
+    }
+
+    /// Returns the words of the points in gnark/EVM order.
+    fn point_words(&self) -> [&[u8; 32]; 8] {
+        [
+            &self.a.x,
+            &self.a.y,
+            &self.b.x[1],
+            &self.b.x[0],
+            &self.b.y[1],
+            &self.b.y[0],
+            &self.c.x,
+            &self.c.y,
+        ]
+    }
+
+    fn from_point_words(words: &[[u8; 32]], public_inputs: Vec<[u8; 32]>) -> Self {
+        let a = G1Point {
+            x: words[0],
+            y: words[1],
+        };
+        let b = G2Point {
+            x: [words[3], words[2]],
+            y: [words[5], words[4]],
+        };
+        let c = G1Point {
+            x: words[6],
+            y: words[7],
+        };
+        Self::new(a, b, c, public_inputs)
+    }
+}
+
+#[cfg(feature = "bn254")]
+impl CanonicalGroth16Proof {
+    /// Returns the points in a compact fixed-size encoding of [`GROTH16_COMPACT_SIZE`] bytes.
+    ///
+    /// Each point is compressed to its `x` coordinate in gnark's compressed format: the two most
+    /// significant bits of the first byte flag whether `y` is the lexicographically smallest
+    /// (`0b10`) or largest (`0b11`) root. The public inputs are not included since they can be
+    /// recomputed by the verifier from the program commitment and the public values.
+    pub fn to_compact_bytes(&self) -> ZkVmResult<[u8; GROTH16_COMPACT_SIZE]> {
+        let mut out = [0u8; GROTH16_COMPACT_SIZE];
+        out[..32].copy_from_slice(&bn254::compress_g1(&self.a)?);
+        out[32..96].copy_from_slice(&bn254::compress_g2(&self.b)?);
+        out[96..].copy_from_slice(&bn254::compress_g1(&self.c)?);
+        Ok(out)
+    }
+
+    /// Decodes the points from [`to_compact_bytes`](Self::to_compact_bytes) encoding, checking
+    /// that they are valid curve points.
+    pub fn from_compact_bytes(bytes: &[u8], public_inputs: Vec<[u8; 32]>) -> ZkVmResult<Self> {
+        if bytes.len() != GROTH16_COMPACT_SIZE {
+            return Err(encoding_error(format!(
+                "expected {} bytes, found {}",
+                GROTH16_COMPACT_SIZE,
+                bytes.len()
+            )));
+        }
+        let a = bn254::decompress_g1(&bytes[..32])?;
+        let b = bn254::decompress_g2(&bytes[32..96])?;
+        let c = bn254::decompress_g1(&bytes[96..])?;
+        Ok(Self::new(a, b, c, public_inputs))
+    }
+}
+
+fn to_word(chunk: &[u8]) -> [u8; 32] {
+    chunk.try_into().expect("chunk is 32 bytes")
+}
+
+fn encoding_error(msg: String) -> crate::ZkVmError {
+    ZkVmProofError::InvalidGroth16Encoding(msg).into()
+}
+
+#[cfg(feature = "bn254")]
+mod bn254 {
+    use alloc::{string::ToString, vec::Vec};
+
+    use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+    use ark_ff::{BigInteger, Field, PrimeField};
+
+    use super::{encoding_error, G1Point, G2Point};
+    use crate::ZkVmResult;
+
+    const FLAG_MASK: u8 = 0b11 << 6;
+    const FLAG_SMALLEST: u8 = 0b10 << 6;
+    const FLAG_LARGEST: u8 = 0b11 << 6;
+
+    fn fq_from_bytes(bytes: &[u8]) -> ZkVmResult<Fq> {
+        let fq = Fq::from_be_bytes_mod_order(bytes);
+        if fq_to_bytes(&fq) != bytes {
+            return Err(encoding_error("non-canonical field element".to_string()));
+        }
+        Ok(fq)
+    }
+
+    fn fq_to_bytes(fq: &Fq) -> [u8; 32] {
+        fq.into_bigint()
+            .to_bytes_be()
+            .try_into()
+            .expect("field element is 32 bytes")
+    }
+
+    fn g1_from_point(point: &G1Point) -> ZkVmResult<G1Affine> {
+        let p = G1Affine::new_unchecked(fq_from_bytes(&point.x)?, fq_from_bytes(&point.y)?);
+        if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(encoding_error("invalid G1 point".to_string()));
+        }
+        Ok(p)
+    }
+
+    fn g2_from_point(point: &G2Point) -> ZkVmResult<G2Affine> {
+        let x = Fq2::new(fq_from_bytes(&point.x[0])?, fq_from_bytes(&point.x[1])?);
+        let y = Fq2::new(fq_from_bytes(&point.y[0])?, fq_from_bytes(&point.y[1])?);
+        let p = G2Affine::new_unchecked(x, y);
+        if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(encoding_error("invalid G2 point".to_string()));
+        }
+        Ok(p)
+    }
+
+    /// Splits the flag bits off the first byte of a compressed coordinate.
+    fn split_flag(bytes: &[u8]) -> (u8, Vec<u8>) {
+        let mut bytes = bytes.to_vec();
+        let flag = bytes[0] & FLAG_MASK;
+        bytes[0] &= !FLAG_MASK;
+        (flag, bytes)
+    }
+
+    fn select_root<F: Field>(y: F, flag: u8) -> ZkVmResult<F> {
+        let neg_y = -y;
+        let (smallest, largest) = if y < neg_y { (y, neg_y) } else { (neg_y, y) };
+        match flag {
+            FLAG_SMALLEST => Ok(smallest),
+            FLAG_LARGEST => Ok(largest),
+            _ => Err(encoding_error("invalid compression flag".to_string())),
+        }
+    }
+
+    pub(super) fn compress_g1(point: &G1Point) -> ZkVmResult<[u8; 32]> {
+        let p = g1_from_point(point)?;
+        let mut out = point.x;
+        out[0] |= if p.y > -p.y {
+            FLAG_LARGEST
+        } else {
+            FLAG_SMALLEST
+        };
+        Ok(out)
+    }
+
+    pub(super) fn compress_g2(point: &G2Point) -> ZkVmResult<[u8; 64]> {
+        let p = g2_from_point(point)?;
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(&point.x[1]);
+        out[32..].copy_from_slice(&point.x[0]);
+        out[0] |= if p.y > -p.y {
+            FLAG_LARGEST
+        } else {
+            FLAG_SMALLEST
+        };
+        Ok(out)
+    }
+
+    pub(super) fn decompress_g1(bytes: &[u8]) -> ZkVmResult<G1Point> {
+        let (flag, x_bytes) = split_flag(bytes);
+        let x = fq_from_bytes(&x_bytes)?;
+        let (y, _) = G1Affine::get_ys_from_x_unchecked(x)
+            .ok_or_else(|| encoding_error("x is not on the G1 curve".to_string()))?;
+        let point = G1Point {
+            x: fq_to_bytes(&x),
+            y: fq_to_bytes(&select_root(y, flag)?),
+        };
+        g1_from_point(&point).map(|_| point)
+    }
+
+    pub(super) fn decompress_g2(bytes: &[u8]) -> ZkVmResult<G2Point> {
+        let (flag, x_bytes) = split_flag(bytes);
+        let x = Fq2::new(
+            fq_from_bytes(&x_bytes[32..])?,
+            fq_from_bytes(&x_bytes[..32])?,
+        );
+        let (y, _) = G2Affine::get_ys_from_x_unchecked(x)
+            .ok_or_else(|| encoding_error("x is not on the G2 curve".to_string()))?;
+        let y = select_root(y, flag)?;
+        let point = G2Point {
+            x: [fq_to_bytes(&x.c0), fq_to_bytes(&x.c1)],
+            y: [fq_to_bytes(&y.c0), fq_to_bytes(&y.c1)],
+        };
+        g2_from_point(&point).map(|_| point)
+    }
+
+    #[cfg(test)]
+    pub(super) fn generators() -> (G1Point, G2Point) {
+        use ark_ec::AffineRepr;
+
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        (
+            G1Point {
+                x: fq_to_bytes(&g1.x),
+                y: fq_to_bytes(&g1.y),
+            },
+            G2Point {
+                x: [fq_to_bytes(&g2.x.c0), fq_to_bytes(&g2.x.c1)],
+                y: [fq_to_bytes(&g2.y.c0), fq_to_bytes(&g2.y.c1)],
+            },
+        )
+    }
+
+    #[cfg(test)]
+    pub(super) fn negate_g1(point: &G1Point) -> G1Point {
+        let y = -fq_from_bytes(&point.y).unwrap();
+        G1Point {
+            x: point.x,
+            y: fq_to_bytes(&y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_proof() -> CanonicalGroth16Proof {
+        let words: Vec<[u8; 32]> = (0..8u8).map(|i| [i + 1; 32]).collect();
+        CanonicalGroth16Proof::from_point_words(&words, vec![[0xaa; 32], [0xbb; 32]])
+    }
+
+    #[test]
+    fn test_gnark_bytes_roundtrip() {
+        let proof = sample_proof();
+        let bytes = proof.to_gnark_bytes();
+
+        // G2 coordinates are stored with the imaginary component first
+        assert_eq!(&bytes[64..96], &proof.b().x[1]);
+
+        let decoded =
+            CanonicalGroth16Proof::from_gnark_bytes(&bytes, proof.public_inputs().to_vec())
+                .unwrap();
+        assert_eq!(decoded, proof);
+        assert!(CanonicalGroth16Proof::from_gnark_bytes(&bytes[1..], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_solidity_calldata_roundtrip() {
+        let proof = sample_proof();
+        let calldata = proof.to_solidity_calldata();
+        assert_eq!(calldata.len(), GROTH16_POINTS_SIZE + 2 * 32);
+        assert_eq!(&calldata[GROTH16_POINTS_SIZE..][..32], &[0xaa; 32]);
+
+        let decoded = CanonicalGroth16Proof::from_solidity_calldata(&calldata).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(CanonicalGroth16Proof::from_solidity_calldata(&calldata[..100]).is_err());
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn test_compact_bytes_roundtrip() {
+        let (g1, g2) = bn254::generators();
+        let public_inputs = vec![[1; 32]];
+
+        // Use both roots of G1 to cover both compression flags
+        let proof = CanonicalGroth16Proof::new(g1, g2, bn254::negate_g1(&g1), public_inputs);
+        let compact = proof.to_compact_bytes().unwrap();
+        let decoded =
+            CanonicalGroth16Proof::from_compact_bytes(&compact, proof.public_inputs().to_vec())
+                .unwrap();
+        assert_eq!(decoded, proof);
+
+        // Points that are not on the curve cannot be compressed
+        assert!(sample_proof().to_compact_bytes().is_err());
+
+        // Flipping the flag selects the other root
+        let mut flipped = compact;
+        flipped[0] ^= 0b01 << 6;
+        let decoded =
+            CanonicalGroth16Proof::from_compact_bytes(&flipped, proof.public_inputs().to_vec())
+                .unwrap();
+        assert_eq!(decoded.a(), &bn254::negate_g1(&g1));
+    }
+}
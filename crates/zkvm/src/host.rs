@@ -4,8 +4,8 @@ use borsh::BorshDeserialize;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    input::ZkVmInputBuilder, ProofReceipt, ProofType, PublicValues, VerificationKey, ZkVmError,
-    ZkVmProofError, ZkVmResult,
+    input::ZkVmInputBuilder, Groth16Proof, ProgramId, ProofReceipt, ProofType, PublicValues,
+    RecordedInput, VerificationKey, ZkVmError, ZkVmProofError, ZkVmResult,
 };
 
 /// A trait implemented by the prover ("host") of a zkVM program.
@@ -33,12 +33,40 @@ pub trait ZkVmHost: Send + Sync + Clone + Display + 'static {
         receipt.try_into().map_err(ZkVmError::InvalidProofReceipt)
     }
 
+    /// Executes the guest code within the VM without generating a proof and returns the
+    /// resulting [`PublicValues`].
+    fn execute<'a>(
+        &self,
+        input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+    ) -> ZkVmResult<PublicValues>;
+
     /// Returns the Verification key for the loaded program
     fn get_verification_key(&self) -> VerificationKey;
 
+    /// Returns the [`ProgramId`] of the loaded program, i.e. the digest a guest passes to
+    /// [`ZkVmEnv::verify_native_proof`](crate::ZkVmEnv::verify_native_proof) to verify its proofs.
+    fn program_id(&self) -> ProgramId;
+
+    /// Returns the 32-byte program commitment that Groth16 proofs of the loaded program are
+    /// verified against.
+    ///
+    /// This is the key expected by the `verify_groth16` functions of the adapters and by
+    /// [`ZkVmEnv::verify_groth16_proof`](crate::ZkVmEnv::verify_groth16_proof): `vk.bytes32()` for
+    /// SP1 and the image id for Risc0.
+    fn get_groth16_verification_key(&self) -> [u8; 32];
+
     /// Returns the ELF for the loaded program
     fn get_elf(&self) -> &[u8];
 
+    /// Returns the name and version of the zkVM backend, e.g. `sp1-v4.0.0-rc.3`.
+    ///
+    /// Proofs generated by different versions are not interchangeable, so this is part of the key
+    /// of cached proofs. See [`ProofCacheKey`](crate::ProofCacheKey). Defaults to `unknown`, with
+    /// which cached proofs survive backend upgrades, so hosts should override it.
+    fn backend_version(&self) -> String {
+        "unknown".to_string()
+    }
+
     /// Extracts the public output from the public values using ZkVm's `serde`
     /// serialization/deserialization.
     fn extract_serde_public_output<T: Serialize + DeserializeOwned>(
@@ -54,6 +82,14 @@ pub trait ZkVmHost: Send + Sync + Clone + Display + 'static {
             .map_err(|e| ZkVmError::OutputExtractionError { source: e.into() })
     }
 
+    /// Extracts the [`Groth16Proof`] from a [`ProofReceipt`] generated with
+    /// [`ProofType::Groth16`].
+    ///
+    /// The seal of the returned proof can be passed as is to the `verify_groth16` function of the
+    /// backend together with [`ZkVmHost::get_groth16_verification_key`] and the public values of
+    /// the receipt.
+    fn extract_groth16_proof(receipt: &ProofReceipt) -> ZkVmResult<Groth16Proof>;
+
     /// Verifies the proof generated by the ZkVm
     fn verify_inner(&self, proof: &Self::ZkVmProofReceipt) -> ZkVmResult<()>;
 
@@ -62,3 +98,82 @@ pub trait ZkVmHost: Send + Sync + Clone + Display + 'static {
         self.verify_inner(&proof.clone().try_into()?)
     }
 }
+
+/// An object-safe counterpart of [`ZkVmHost`].
+///
+/// [`ZkVmHost`] relies on a generic associated input type, so it cannot be used as a trait
+/// object. `DynZkVmHost` instead takes a backend-neutral [`RecordedInput`], which allows hosts of
+/// different backends to be stored together, e.g. in a `HashMap<ZkVm, Box<dyn DynZkVmHost>>`,
+/// and picked at runtime. It is implemented for every [`ZkVmHost`], with method names distinct
+/// from those of [`ZkVmHost`] so that both traits can be in scope for a concrete host.
+pub trait DynZkVmHost: Send + Sync + Display {
+    /// Replays the input into the host's input builder and generates a [`ProofReceipt`].
+    fn prove_recorded(
+        &self,
+        input: &RecordedInput,
+        proof_type: ProofType,
+    ) -> ZkVmResult<ProofReceipt>;
+
+    /// Replays the input into the host's input builder and executes the guest code without
+    /// generating a proof.
+    fn execute_recorded(&self, input: &RecordedInput) -> ZkVmResult<PublicValues>;
+
+    /// Verifies the [`ProofReceipt`].
+    fn verify_receipt(&self, proof: &ProofReceipt) -> ZkVmResult<()>;
+
+    /// Returns the Verification key for the loaded program
+    fn verification_key(&self) -> VerificationKey;
+
+    /// Returns the [`ProgramId`] of the loaded program
+    fn guest_program_id(&self) -> ProgramId;
+
+    /// Returns the 32-byte program commitment that Groth16 proofs of the loaded program are
+    /// verified against.
+    fn groth16_verification_key(&self) -> [u8; 32];
+
+    /// Returns the ELF for the loaded program
+    fn elf(&self) -> &[u8];
+
+    /// Returns the name and version of the zkVM backend.
+    fn zkvm_version(&self) -> String;
+}
+
+impl<H: ZkVmHost> DynZkVmHost for H {
+    fn prove_recorded(
+        &self,
+        input: &RecordedInput,
+        proof_type: ProofType,
+    ) -> ZkVmResult<ProofReceipt> {
+        let zkvm_input = input.replay::<H::Input<'_>>()?;
+        ZkVmHost::prove(self, zkvm_input, proof_type)
+    }
+
+    fn execute_recorded(&self, input: &RecordedInput) -> ZkVmResult<PublicValues> {
+        let zkvm_input = input.replay::<H::Input<'_>>()?;
+        ZkVmHost::execute(self, zkvm_input)
+    }
+
+    fn verify_receipt(&self, proof: &ProofReceipt) -> ZkVmResult<()> {
+        ZkVmHost::verify(self, proof)
+    }
+
+    fn verification_key(&self) -> VerificationKey {
+        ZkVmHost::get_verification_key(self)
+    }
+
+    fn guest_program_id(&self) -> ProgramId {
+        ZkVmHost::program_id(self)
+    }
+
+    fn groth16_verification_key(&self) -> [u8; 32] {
+        ZkVmHost::get_groth16_verification_key(self)
+    }
+
+    fn elf(&self) -> &[u8] {
+        ZkVmHost::get_elf(self)
+    }
+
+    fn zkvm_version(&self) -> String {
+        ZkVmHost::backend_version(self)
+    }
+}
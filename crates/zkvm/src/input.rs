@@ -1,4 +1,7 @@
-use crate::{AggregationInput, ProofReceipt, ZkVmInputError};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{AggregationInput, ProofReceipt, ProofType, ZkVmInputError};
 
 /// A convenient alias for results in the ZkVM.
 pub type ZkVmInputResult<T> = Result<T, ZkVmInputError>;
@@ -13,6 +16,9 @@ pub trait ZkVmInputBuilder<'a> {
     fn new() -> Self;
 
     /// Serializes the given item using Serde and appends it to the list of inputs.
+    ///
+    /// Every backend encodes the item with `bincode`, so this is equivalent to calling
+    /// [`write_buf`](ZkVmInputBuilder::write_buf) with the bincode-serialized item.
     fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self>;
 
     /// Serializes the given item using the Borsh serialization format and appends
@@ -30,7 +36,119 @@ pub trait ZkVmInputBuilder<'a> {
     ///
     /// This method is specifically used for cases where proof aggregation or composition
     /// is involved, allowing for proof and verification inputs to be provided to the zkVM.
+    ///
+    /// # Frame layout
+    ///
+    /// Every backend writes exactly one guest-visible frame per proof: the public values of the
+    /// proven program, as if written with [`write_buf`](ZkVmInputBuilder::write_buf). The proof
+    /// and the verification key are never part of the input stream. They are handed to the
+    /// backend out-of-band instead: as a deferred proof in SP1, as an assumption in Risc0, and not
+    /// at all in native mode.
+    ///
+    /// The frame is meant to be consumed in the guest with
+    /// [`read_verified_buf`](crate::ZkVmEnv::read_verified_buf) or one of its typed variants,
+    /// which verify it against the verification key digest supplied by the guest.
     fn write_proof(&mut self, item: &AggregationInput) -> ZkVmInputResult<&mut Self>;
 
+    /// Returns the type of the proofs accepted by [`write_proof`](ZkVmInputBuilder::write_proof).
+    fn aggregated_proof_type() -> ProofType {
+        ProofType::Core
+    }
+
     fn build(&mut self) -> ZkVmInputResult<Self::Input>;
 }
+
+/// A single input item recorded by [`ZkVmInputRecorder`].
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum RecordedInputItem {
+    /// An item written with [`ZkVmInputBuilder::write_serde`], stored bincode-encoded.
+    Serde(Vec<u8>),
+    /// An item written with [`ZkVmInputBuilder::write_borsh`], stored borsh-encoded.
+    Borsh(Vec<u8>),
+    /// A pre-serialized buffer written with [`ZkVmInputBuilder::write_buf`].
+    Buf(Vec<u8>),
+    /// A proof written with [`ZkVmInputBuilder::write_proof`].
+    Proof(AggregationInput),
+}
+
+/// A backend-neutral input to a zkVM program.
+///
+/// A `RecordedInput` is produced by [`ZkVmInputRecorder`] and can be replayed into the input
+/// builder of any backend with [`RecordedInput::replay`]. This allows the input to be prepared
+/// once, stored or sent elsewhere, and then proven with a host that is only chosen at runtime.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq, Default,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RecordedInput(Vec<RecordedInputItem>);
+
+impl RecordedInput {
+    /// Creates a new `RecordedInput` from the given items.
+    pub fn new(items: Vec<RecordedInputItem>) -> Self {
+        Self(items)
+    }
+
+    /// Returns the recorded items in the order they were written.
+    pub fn items(&self) -> &[RecordedInputItem] {
+        &self.0
+    }
+
+    /// Writes all the recorded items into a fresh input builder `B` and builds the input.
+    pub fn replay<'a, B>(&'a self) -> ZkVmInputResult<B::Input>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        let mut builder = B::new();
+        for item in &self.0 {
+            match item {
+                // Serde items are bincode-encoded by every backend.
+                RecordedInputItem::Serde(buf) => builder.write_buf(buf)?,
+                RecordedInputItem::Borsh(buf) => builder.write_buf(buf)?,
+                RecordedInputItem::Buf(buf) => builder.write_buf(buf)?,
+                RecordedInputItem::Proof(proof) => builder.write_proof(proof)?,
+            };
+        }
+        builder.build()
+    }
+}
+
+/// A [`ZkVmInputBuilder`] that records the written items into a [`RecordedInput`] instead of
+/// feeding them to a particular zkVM.
+#[derive(Debug, Clone, Default)]
+pub struct ZkVmInputRecorder(Vec<RecordedInputItem>);
+
+impl ZkVmInputBuilder<'_> for ZkVmInputRecorder {
+    type Input = RecordedInput;
+    type ZkVmProofReceipt = ProofReceipt;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
+        let buf = bincode::serialize(item)?;
+        self.0.push(RecordedInputItem::Serde(buf));
+        Ok(self)
+    }
+
+    fn write_borsh<T: borsh::BorshSerialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
+        let buf = borsh::to_vec(item)?;
+        self.0.push(RecordedInputItem::Borsh(buf));
+        Ok(self)
+    }
+
+    fn write_buf(&mut self, item: &[u8]) -> ZkVmInputResult<&mut Self> {
+        self.0.push(RecordedInputItem::Buf(item.to_vec()));
+        Ok(self)
+    }
+
+    fn write_proof(&mut self, item: &AggregationInput) -> ZkVmInputResult<&mut Self> {
+        self.0.push(RecordedInputItem::Proof(item.clone()));
+        Ok(self)
+    }
+
+    fn build(&mut self) -> ZkVmInputResult<Self::Input> {
+        Ok(RecordedInput(self.0.clone()))
+    }
+}
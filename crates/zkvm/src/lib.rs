@@ -1,23 +1,61 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "std")]
+mod backend;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "arbitrary")]
+mod differential;
+mod elf;
 mod env;
 mod errors;
+#[cfg(feature = "std")]
+mod fixtures;
+mod groth16;
+#[cfg(feature = "std")]
 mod host;
+#[cfg(feature = "std")]
 mod input;
 #[cfg(feature = "perf")]
 mod perf;
+#[cfg(feature = "std")]
+mod pipeline;
+mod program_id;
 mod proof;
+#[cfg(feature = "std")]
 mod prover;
+mod verifier;
 
+#[cfg(feature = "std")]
+pub use backend::*;
+#[cfg(feature = "std")]
+pub use cache::*;
+#[cfg(feature = "arbitrary")]
+pub use differential::*;
+pub use elf::*;
 pub use env::*;
 pub use errors::*;
+#[cfg(feature = "std")]
+pub use fixtures::*;
+pub use groth16::*;
+#[cfg(feature = "std")]
 pub use host::*;
+#[cfg(feature = "std")]
 pub use input::*;
 #[cfg(feature = "perf")]
 pub use perf::*;
+#[cfg(feature = "std")]
+pub use pipeline::*;
+pub use program_id::*;
 pub use proof::*;
+#[cfg(feature = "std")]
 pub use prover::*;
+pub use verifier::*;
 
 /// Represents the ZkVm host used for proof generation.
 ///
@@ -33,11 +71,14 @@ pub use prover::*;
     PartialEq,
     Eq,
     Hash,
+    PartialOrd,
+    Ord,
     BorshSerialize,
     BorshDeserialize,
     Serialize,
     Deserialize,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ZkVm {
     SP1,
     Risc0,
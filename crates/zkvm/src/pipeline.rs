@@ -0,0 +1,122 @@
+use crate::{
+    host::ZkVmHost, input::ZkVmInputBuilder, AggregationInput, ProofReceipt, ProofType, ZkVmProver,
+    ZkVmResult,
+};
+
+/// A proof that can be aggregated by a [`ProofPipeline`].
+///
+/// Implemented by [`ProgramProof`] for the proof of a single program and by [`ProofPipeline`]
+/// itself, so that pipelines can be nested into trees of aggregations.
+pub trait ProofNode {
+    /// Proves the node with the given proof type and returns the proof along with the
+    /// verification key it is checked against.
+    fn prove_node(&self, proof_type: ProofType) -> ZkVmResult<AggregationInput>;
+}
+
+/// The proof of a program declared with its [`ZkVmProver`], its input and its host.
+pub struct ProgramProof<P: ZkVmProver, H: ZkVmHost> {
+    input: P::Input,
+    host: H,
+}
+
+impl<P: ZkVmProver, H: ZkVmHost> ProgramProof<P, H> {
+    /// Creates the proof of `input` with `host`.
+    pub fn new(input: P::Input, host: H) -> Self {
+        Self { input, host }
+    }
+}
+
+impl<P: ZkVmProver, H: ZkVmHost> ProofNode for ProgramProof<P, H> {
+    fn prove_node(&self, proof_type: ProofType) -> ZkVmResult<AggregationInput> {
+        let receipt = prove_with_type::<P, H>(&self.input, &self.host, proof_type)?;
+        Ok(AggregationInput::new(
+            receipt,
+            self.host.program_id(),
+            self.host.get_verification_key(),
+        ))
+    }
+}
+
+/// A proof aggregating the proofs of other programs.
+///
+/// The children are declared with [`ProofPipeline::child`] and the aggregation program with its
+/// [`ZkVmProver`], its host and a function building its input from the [`AggregationInput`]s of
+/// the children. Proving the pipeline proves each child with the proof type the aggregation
+/// host accepts in [`ZkVmInputBuilder::write_proof`], e.g. [`ProofType::Compressed`] for SP1,
+/// and then proves the aggregation program.
+///
+/// With `NativeHost`s for the children and the aggregation program, the whole pipeline runs
+/// natively, which is meant for fast tests of the aggregation logic.
+pub struct ProofPipeline<P: ZkVmProver, H: ZkVmHost> {
+    host: H,
+    build_input: Box<dyn Fn(Vec<AggregationInput>) -> P::Input>,
+    children: Vec<Box<dyn ProofNode>>,
+}
+
+impl<P: ZkVmProver, H: ZkVmHost> ProofPipeline<P, H> {
+    /// Creates a pipeline aggregating proofs with `host`. The input of the aggregation program is
+    /// built by `build_input` from the proofs of the children, in the order they were added.
+    pub fn new(host: H, build_input: impl Fn(Vec<AggregationInput>) -> P::Input + 'static) -> Self {
+        Self {
+            host,
+            build_input: Box::new(build_input),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child proof, e.g. a [`ProgramProof`] or another pipeline.
+    pub fn child(mut self, child: impl ProofNode + 'static) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Checks if the pipeline has no children.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Proves the children and then the aggregation program with [`ZkVmProver::proof_type`].
+    pub fn prove(&self) -> ZkVmResult<ProofReceipt> {
+        self.prove_with_type(P::proof_type())
+    }
+
+    fn prove_with_type(&self, proof_type: ProofType) -> ZkVmResult<ProofReceipt> {
+        let child_proof_type = <H::Input<'_> as ZkVmInputBuilder<'_>>::aggregated_proof_type();
+        let proofs = self
+            .children
+            .iter()
+            .map(|child| child.prove_node(child_proof_type))
+            .collect::<ZkVmResult<Vec<_>>>()?;
+
+        let input = (self.build_input)(proofs);
+        prove_with_type::<P, H>(&input, &self.host, proof_type)
+    }
+}
+
+impl<P: ZkVmProver, H: ZkVmHost> ProofNode for ProofPipeline<P, H> {
+    fn prove_node(&self, proof_type: ProofType) -> ZkVmResult<AggregationInput> {
+        let receipt = self.prove_with_type(proof_type)?;
+        Ok(AggregationInput::new(
+            receipt,
+            self.host.program_id(),
+            self.host.get_verification_key(),
+        ))
+    }
+}
+
+/// Proves like [`ZkVmProver::prove`] but with the given proof type.
+fn prove_with_type<'a, P: ZkVmProver, H: ZkVmHost>(
+    input: &'a P::Input,
+    host: &H,
+    proof_type: ProofType,
+) -> ZkVmResult<ProofReceipt> {
+    let zkvm_input = P::prepare_input::<H::Input<'a>>(input)?;
+    let receipt = host.prove(zkvm_input, proof_type)?;
+    let _ = P::process_output::<H>(receipt.public_values())?;
+    Ok(receipt)
+}
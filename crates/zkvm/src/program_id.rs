@@ -0,0 +1,142 @@
+use alloc::string::{String, ToString};
+use core::{fmt, str::FromStr};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ZkVmVerificationKeyError;
+
+/// The identity of a zkVM program as seen from within a guest.
+///
+/// This is the digest guests pass to [`ZkVmEnv::verify_native_proof`](crate::ZkVmEnv) to verify a
+/// proof of another program: `vk.hash_u32()` for SP1 and the image id for Risc0. It is obtained on
+/// the host side with [`ZkVmHost::program_id`](crate::ZkVmHost::program_id).
+///
+/// The byte representation is the little-endian encoding of the words, which matches the byte
+/// representation of the Risc0 image id. It is formatted as a hex string.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ProgramId([u32; 8]);
+
+impl ProgramId {
+    /// Creates a new `ProgramId` from its words.
+    pub const fn new(words: [u32; 8]) -> Self {
+        Self(words)
+    }
+
+    /// Returns a reference to the inner words.
+    pub fn as_words(&self) -> &[u32; 8] {
+        &self.0
+    }
+
+    /// Creates a new `ProgramId` from the little-endian encoding of its words.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        let mut words = [0u32; 8];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().expect("chunk is 4 bytes"));
+        }
+        Self(words)
+    }
+
+    /// Returns the little-endian encoding of the words.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(self.0.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+impl From<[u32; 8]> for ProgramId {
+    fn from(words: [u32; 8]) -> Self {
+        Self(words)
+    }
+}
+
+impl From<ProgramId> for [u32; 8] {
+    fn from(value: ProgramId) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<&[u8]> for ProgramId {
+    type Error = ZkVmVerificationKeyError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; 32] = value
+            .try_into()
+            .map_err(|_| ZkVmVerificationKeyError::InvalidVerificationKeySize)?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl fmt::Display for ProgramId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for ProgramId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProgramId({})", self)
+    }
+}
+
+impl FromStr for ProgramId {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes)?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl Serialize for ProgramId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            Serialize::serialize(&self.0, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ProgramId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s: String = Deserialize::deserialize(deserializer)?;
+            s.parse().map_err(de::Error::custom)
+        } else {
+            Deserialize::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_id_bytes_roundtrip() {
+        let id = ProgramId::new([1, 2, 3, 4, 5, 6, 7, u32::MAX]);
+        assert_eq!(ProgramId::from_bytes(id.to_bytes()), id);
+        assert_eq!(&id.to_bytes()[..8], &[1, 0, 0, 0, 2, 0, 0, 0]);
+        assert!(ProgramId::try_from(&id.to_bytes()[..31]).is_err());
+    }
+
+    #[test]
+    fn test_program_id_hex_roundtrip() {
+        let id = ProgramId::new([0xdeadbeef, 1, 2, 3, 4, 5, 6, 7]);
+        let hex = id.to_string();
+        assert!(hex.starts_with("efbeadde01000000"));
+        assert_eq!(hex.parse::<ProgramId>().unwrap(), id);
+        assert_eq!(format!("0x{hex}").parse::<ProgramId>().unwrap(), id);
+        assert!("00".parse::<ProgramId>().is_err());
+
+        let bytes = bincode::serialize(&id).unwrap();
+        assert_eq!(bincode::deserialize::<ProgramId>(&bytes).unwrap(), id);
+    }
+}
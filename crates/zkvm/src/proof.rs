@@ -1,7 +1,12 @@
-use arbitrary::Arbitrary;
+use alloc::vec::Vec;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "std")]
+use crate::ZkVmProofError;
+use crate::{ProgramId, ZkVm};
+
 /// Macro to define a newtype wrapper around `Vec<u8>` with common implementations.
 macro_rules! define_byte_wrapper {
     ($name:ident) => {
@@ -14,9 +19,9 @@ macro_rules! define_byte_wrapper {
             BorshDeserialize,
             PartialEq,
             Eq,
-            Arbitrary,
             Default,
         )]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub struct $name(Vec<u8>);
 
         impl $name {
@@ -67,9 +72,8 @@ define_byte_wrapper!(PublicValues);
 define_byte_wrapper!(VerificationKey);
 
 /// A receipt containing a `Proof` and associated `PublicValues`.
-#[derive(
-    Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq, Arbitrary,
-)]
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProofReceipt {
     /// The validity proof.
     proof: Proof,
@@ -97,21 +101,65 @@ impl ProofReceipt {
     }
 }
 
+/// A Groth16 proof extracted from a [`ProofReceipt`] of [`ProofType::Groth16`].
+///
+/// Consists of the seal, i.e. the Groth16 proof bytes in the format expected by the
+/// `verify_groth16` function of the backend that produced it, and the SHA-256 digest of the public
+/// values it commits to.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Groth16Proof {
+    /// The Groth16 proof bytes.
+    seal: Proof,
+    /// The SHA-256 digest of the public values of the proven program.
+    public_values_digest: [u8; 32],
+}
+
+impl Groth16Proof {
+    /// Creates a new `Groth16Proof` from the seal and the digest of the public values.
+    pub fn new(seal: Proof, public_values_digest: [u8; 32]) -> Self {
+        Self {
+            seal,
+            public_values_digest,
+        }
+    }
+
+    /// Returns the Groth16 proof bytes.
+    pub fn seal(&self) -> &Proof {
+        &self.seal
+    }
+
+    /// Returns the SHA-256 digest of the public values of the proven program.
+    pub fn public_values_digest(&self) -> &[u8; 32] {
+        &self.public_values_digest
+    }
+}
+
 /// An input to the aggregation program.
 ///
-/// Consists of a [`ProofReceipt`] and a [`VerificationKey`].
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Consists of a [`ProofReceipt`], the [`ProgramId`] of the proven program and its
+/// [`VerificationKey`].
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AggregationInput {
     /// The proof receipt containing the proof and its public values.
     receipt: ProofReceipt,
-    /// The verification key for validating the proof.
+    /// The program the proof is verified against.
+    program_id: ProgramId,
+    /// The backend-specific verification key of the program, which SP1 needs to verify the proof
+    /// recursively.
     vk: VerificationKey,
 }
 
 impl AggregationInput {
-    /// Creates a new `AggregationInput`.
-    pub fn new(receipt: ProofReceipt, vk: VerificationKey) -> Self {
-        Self { receipt, vk }
+    /// Creates a new `AggregationInput`, as returned by `ZkVmHost::program_id` and
+    /// `ZkVmHost::get_verification_key` for the host that generated `receipt`.
+    pub fn new(receipt: ProofReceipt, program_id: ProgramId, vk: VerificationKey) -> Self {
+        Self {
+            receipt,
+            program_id,
+            vk,
+        }
     }
 
     /// Returns a reference to the `ProofReceipt`.
@@ -119,14 +167,93 @@ impl AggregationInput {
         &self.receipt
     }
 
+    /// Returns the [`ProgramId`] of the proven program.
+    pub fn program_id(&self) -> ProgramId {
+        self.program_id
+    }
+
     /// Returns a reference to the `VerificationKey`.
     pub fn vk(&self) -> &VerificationKey {
         &self.vk
     }
 }
 
+/// A batch of [`AggregationInput`]s destined for a single aggregation program.
+///
+/// Besides the inputs, the batch carries the identity of the aggregation program that is expected
+/// to consume them, so that a batch can be persisted or handed to a prover worker as a
+/// self-contained job.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AggregationBatch {
+    /// The zkVM the aggregation program runs on.
+    zkvm: ZkVm,
+    /// The identity of the aggregation program.
+    program_id: ProgramId,
+    /// The inputs to be aggregated, in the order the aggregation program reads them.
+    inputs: Vec<AggregationInput>,
+}
+
+impl AggregationBatch {
+    /// Creates a new `AggregationBatch` for the aggregation program identified by `zkvm` and
+    /// `program_id`.
+    pub fn new(zkvm: ZkVm, program_id: ProgramId, inputs: Vec<AggregationInput>) -> Self {
+        Self {
+            zkvm,
+            program_id,
+            inputs,
+        }
+    }
+
+    /// Returns the zkVM the aggregation program runs on.
+    pub fn zkvm(&self) -> ZkVm {
+        self.zkvm
+    }
+
+    /// Returns the identity of the aggregation program.
+    pub fn program_id(&self) -> ProgramId {
+        self.program_id
+    }
+
+    /// Returns the inputs to be aggregated.
+    pub fn inputs(&self) -> &[AggregationInput] {
+        &self.inputs
+    }
+
+    /// Appends an input to the batch.
+    pub fn push(&mut self, input: AggregationInput) {
+        self.inputs.push(input);
+    }
+
+    /// Returns the number of inputs in the batch.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Checks if the batch has no inputs.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Consumes the batch and returns the inputs.
+    pub fn into_inputs(self) -> Vec<AggregationInput> {
+        self.inputs
+    }
+}
+
 /// Enumeration of proof types supported by the system.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
 pub enum ProofType {
     /// Represents a Groth16 proof.
     Groth16,
@@ -135,3 +262,79 @@ pub enum ProofType {
     /// Represents a compressed proof.
     Compressed,
 }
+
+/// The maximum number of bytes read by [`deserialize_proof`].
+#[cfg(feature = "std")]
+pub const MAX_PROOF_SIZE: u64 = 1 << 30;
+
+/// Decodes a bincode-encoded proof of a zkVM SDK, e.g. from the [`Proof`] of a [`ProofReceipt`].
+///
+/// The encoding is the one of `bincode::deserialize`, but decoding fails instead of reading more
+/// than [`MAX_PROOF_SIZE`] bytes, which bounds the allocations an untrusted proof can cause.
+#[cfg(feature = "std")]
+pub fn deserialize_proof<T: serde::de::DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<T, ZkVmProofError> {
+    use bincode::Options;
+
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(MAX_PROOF_SIZE)
+        .deserialize(bytes)
+        .map_err(ZkVmProofError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregation_input(seed: u8) -> AggregationInput {
+        let receipt = ProofReceipt::new(
+            Proof::new(vec![seed; 4]),
+            PublicValues::new(vec![seed.wrapping_add(1); 8]),
+        );
+        AggregationInput::new(
+            receipt,
+            ProgramId::new([seed as u32; 8]),
+            VerificationKey::new(vec![seed; 32]),
+        )
+    }
+
+    #[test]
+    fn test_aggregation_batch_roundtrip() {
+        let mut batch = AggregationBatch::new(
+            ZkVm::SP1,
+            ProgramId::new([7; 8]),
+            vec![aggregation_input(1)],
+        );
+        batch.push(aggregation_input(2));
+        assert_eq!(batch.len(), 2);
+
+        let borsh_bytes = borsh::to_vec(&batch).unwrap();
+        let decoded: AggregationBatch = borsh::from_slice(&borsh_bytes).unwrap();
+        assert_eq!(decoded, batch);
+
+        let bincode_bytes = bincode::serialize(&batch).unwrap();
+        let decoded: AggregationBatch = bincode::deserialize(&bincode_bytes).unwrap();
+        assert_eq!(decoded, batch);
+    }
+
+    #[test]
+    fn test_deserialize_proof() {
+        // Same encoding as `bincode::deserialize`, including trailing bytes
+        let value = (7u32, vec![1u8, 2, 3], "proof".to_string());
+        let mut bytes = bincode::serialize(&value).unwrap();
+        bytes.push(0);
+        let decoded: (u32, Vec<u8>, String) = deserialize_proof(&bytes).unwrap();
+        assert_eq!(decoded, value);
+
+        // A length above the limit fails before reading the items
+        let bytes = (MAX_PROOF_SIZE + 1).to_le_bytes();
+        assert!(matches!(
+            deserialize_proof::<Vec<u8>>(&bytes),
+            Err(ZkVmProofError::DataFormat(_))
+        ));
+        assert!(deserialize_proof::<Vec<u64>>(&u64::MAX.to_le_bytes()).is_err());
+    }
+}
@@ -1,7 +1,16 @@
+#[cfg(feature = "std")]
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
 use crate::{
     host::ZkVmHost, input::ZkVmInputBuilder, ProofReceipt, ProofType, PublicValues,
     ZkVmInputResult, ZkVmResult,
 };
+#[cfg(feature = "std")]
+use crate::{ProofCache, ProofCacheKey, ZkVmError, ZkVmInputRecorder};
 
 pub trait ZkVmProver {
     type Input;
@@ -36,4 +45,75 @@ pub trait ZkVmProver {
 
         Ok(receipt)
     }
+
+    /// Proves the computation like [`ZkVmProver::prove`], reusing the proof cached in `cache` for
+    /// the same program, input, proof type and backend version if it passes verification.
+    ///
+    /// New proofs are stored in the cache. Failing to store a proof does not fail proving. Hosts
+    /// without a program id, like `NativeHost`, always prove. See [`ProofCacheKey::new`].
+    #[cfg(feature = "std")]
+    fn prove_cached<'a, H>(
+        input: &'a Self::Input,
+        host: &H,
+        cache: &ProofCache,
+    ) -> ZkVmResult<ProofReceipt>
+    where
+        H: ZkVmHost,
+        H::Input<'a>: ZkVmInputBuilder<'a>,
+    {
+        // The cache is keyed by the backend-neutral recording of the input
+        let recorded_input = Self::prepare_input::<ZkVmInputRecorder>(input)?;
+        let Some(key) = ProofCacheKey::new(host, &recorded_input, Self::proof_type()) else {
+            return Self::prove(input, host);
+        };
+        if let Some(receipt) = cache.get(&key, host) {
+            return Ok(receipt);
+        }
+
+        let receipt = Self::prove(input, host)?;
+        let _ = cache.put(&key, &receipt);
+        Ok(receipt)
+    }
+
+    /// Proves each of `inputs` like [`ZkVmProver::prove`], running at most `parallelism` proofs at
+    /// a time.
+    ///
+    /// Returns one result per input, in the order of `inputs`. A failing or panicking proof only
+    /// fails its own item, the rest of the batch is still proven.
+    #[cfg(feature = "std")]
+    fn prove_batch<'a, H>(
+        inputs: &'a [Self::Input],
+        host: &H,
+        parallelism: usize,
+    ) -> Vec<ZkVmResult<ProofReceipt>>
+    where
+        H: ZkVmHost,
+        H::Input<'a>: ZkVmInputBuilder<'a>,
+        Self::Input: Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let prove_next = || {
+            let mut results = Vec::new();
+            loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(input) = inputs.get(index) else {
+                    return results;
+                };
+                let res = panic::catch_unwind(panic::AssertUnwindSafe(|| Self::prove(input, host)))
+                    .unwrap_or_else(|_| Err(ZkVmError::Other("prover panicked".to_string())));
+                results.push((index, res));
+            }
+        };
+
+        let workers = parallelism.clamp(1, inputs.len().max(1));
+        let mut results: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers).map(|_| scope.spawn(prove_next)).collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("panics are caught"))
+                .collect()
+        });
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, res)| res).collect()
+    }
 }
@@ -0,0 +1,105 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+
+use crate::{Proof, ZkVm, ZkVmError, ZkVmResult};
+
+/// A verifier of Groth16 proofs produced by a zkVM backend.
+///
+/// All implementations share the same argument semantics, so that proofs from any backend can be
+/// verified through this interface:
+///
+/// * `proof`: the seal of a [`Groth16Proof`](crate::Groth16Proof).
+/// * `verification_key`: the 32-byte program commitment, as returned by
+///   [`ZkVmHost::get_groth16_verification_key`](crate::ZkVmHost::get_groth16_verification_key).
+/// * `public_values`: the raw public values committed by the program. Implementations hash them as
+///   required by the backend.
+pub trait Groth16Verifier {
+    /// Verifies the Groth16 proof against the program commitment and the public values.
+    fn verify_groth16(
+        &self,
+        proof: &Proof,
+        verification_key: &[u8; 32],
+        public_values: &[u8],
+    ) -> ZkVmResult<()>;
+}
+
+/// Dispatches Groth16 verification to the [`Groth16Verifier`] registered for a [`ZkVm`].
+#[derive(Default)]
+pub struct Groth16VerifierRegistry {
+    verifiers: BTreeMap<ZkVm, Box<dyn Groth16Verifier + Send + Sync>>,
+}
+
+impl Groth16VerifierRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the verifier for the given [`ZkVm`], replacing any previously registered one.
+    pub fn register<V>(&mut self, zkvm: ZkVm, verifier: V) -> &mut Self
+    where
+        V: Groth16Verifier + Send + Sync + 'static,
+    {
+        self.verifiers.insert(zkvm, Box::new(verifier));
+        self
+    }
+
+    /// Returns the verifier registered for the given [`ZkVm`], if any.
+    pub fn get(&self, zkvm: ZkVm) -> Option<&(dyn Groth16Verifier + Send + Sync)> {
+        self.verifiers.get(&zkvm).map(|v| v.as_ref())
+    }
+
+    /// Verifies a Groth16 proof produced by the given [`ZkVm`].
+    ///
+    /// Returns [`ZkVmError::UnsupportedZkVm`] if no verifier is registered for it.
+    pub fn verify_groth16(
+        &self,
+        zkvm: ZkVm,
+        proof: &Proof,
+        verification_key: &[u8; 32],
+        public_values: &[u8],
+    ) -> ZkVmResult<()> {
+        self.get(zkvm)
+            .ok_or(ZkVmError::UnsupportedZkVm(zkvm))?
+            .verify_groth16(proof, verification_key, public_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts proofs whose bytes equal the verification key.
+    struct EchoVerifier;
+
+    impl Groth16Verifier for EchoVerifier {
+        fn verify_groth16(
+            &self,
+            proof: &Proof,
+            verification_key: &[u8; 32],
+            _public_values: &[u8],
+        ) -> ZkVmResult<()> {
+            if proof.as_bytes() == verification_key {
+                Ok(())
+            } else {
+                Err(ZkVmError::ProofVerificationError("mismatch".to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatch() {
+        let mut registry = Groth16VerifierRegistry::new();
+        registry.register(ZkVm::SP1, EchoVerifier);
+
+        let vk = [3u8; 32];
+        let proof = Proof::new(vk.to_vec());
+        assert!(registry.verify_groth16(ZkVm::SP1, &proof, &vk, &[]).is_ok());
+        assert!(registry
+            .verify_groth16(ZkVm::SP1, &Proof::default(), &vk, &[])
+            .is_err());
+        assert!(matches!(
+            registry.verify_groth16(ZkVm::Risc0, &proof, &vk, &[]),
+            Err(ZkVmError::UnsupportedZkVm(ZkVm::Risc0))
+        ));
+    }
+}
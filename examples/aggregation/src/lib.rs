@@ -0,0 +1,184 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_zkvm::{
+    AggregationInput, ProgramId, ProofType, ZkVmEnv, ZkVmInputResult, ZkVmProver, ZkVmProverPerf,
+};
+
+/// Verifies the proofs of the fibonacci program and commits their outputs.
+///
+/// The guest reads the [`ProgramId`] of the fibonacci program, the number of proofs and then the
+/// public values of each proof, verified against that program id. The program id is committed
+/// along with the outputs, so whoever verifies the aggregation proof must check it against the
+/// fibonacci program they expect.
+pub fn process_fib_aggregation(zkvm: &impl ZkVmEnv) {
+    let fib_program_id: ProgramId = zkvm.read_borsh();
+    let n: u32 = zkvm.read_serde();
+
+    let outputs: Vec<i32> = (0..n)
+        .map(|_| zkvm.read_verified_serde(&fib_program_id))
+        .collect();
+    let sum = outputs.iter().map(|&output| output as i64).sum();
+
+    zkvm.commit_borsh(&FibAggregationOutput {
+        fib_program_id,
+        outputs,
+        sum,
+    });
+}
+
+/// The input of the aggregation program.
+#[derive(Debug, Clone)]
+pub struct FibAggregationInput {
+    /// The program id of the fibonacci program, as returned by `ZkVmHost::program_id`.
+    pub fib_program_id: ProgramId,
+    /// The proofs of the fibonacci program, generated with the proof type accepted by the
+    /// aggregation host.
+    pub proofs: Vec<AggregationInput>,
+}
+
+/// The output committed by the aggregation program.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct FibAggregationOutput {
+    /// The program id the proofs were verified against.
+    pub fib_program_id: ProgramId,
+    /// The outputs of the proofs, in the order of the input.
+    pub outputs: Vec<i32>,
+    /// The sum of the outputs.
+    pub sum: i64,
+}
+
+pub struct FibAggregationProver;
+
+impl ZkVmProver for FibAggregationProver {
+    type Input = FibAggregationInput;
+    type Output = FibAggregationOutput;
+
+    fn proof_type() -> strata_zkvm::ProofType {
+        ProofType::Compressed
+    }
+
+    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+    where
+        B: strata_zkvm::ZkVmInputBuilder<'a>,
+    {
+        let mut input_builder = B::new();
+        input_builder.write_borsh(&input.fib_program_id)?;
+        input_builder.write_serde(&(input.proofs.len() as u32))?;
+        for proof in &input.proofs {
+            input_builder.write_proof(proof)?;
+        }
+        input_builder.build()
+    }
+
+    fn process_output<H>(
+        public_values: &strata_zkvm::PublicValues,
+    ) -> strata_zkvm::ZkVmResult<Self::Output>
+    where
+        H: strata_zkvm::ZkVmHost,
+    {
+        H::extract_borsh_public_output(public_values)
+    }
+}
+
+impl ZkVmProverPerf for FibAggregationProver {}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic, sync::Arc};
+
+    use fibonacci::{process_fib, FibProver};
+    use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
+    use strata_zkvm::{
+        DynZkVmHost, ProgramProof, ProofPipeline, ZkVmHost, ZkVmInputBuilder, ZkVmInputRecorder,
+        ZkVmProver,
+    };
+
+    use super::*;
+
+    fn get_native_fib_host() -> NativeHost {
+        NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_fib(zkvm);
+                Ok(())
+            })),
+        }
+    }
+
+    fn get_native_host() -> NativeHost {
+        NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_fib_aggregation(zkvm);
+                Ok(())
+            })),
+        }
+    }
+
+    fn fib_pipeline(inputs: &[u32]) -> ProofPipeline<FibAggregationProver, NativeHost> {
+        let fib_host = get_native_fib_host();
+        let fib_program_id = fib_host.program_id();
+        inputs.iter().fold(
+            ProofPipeline::new(get_native_host(), move |proofs| FibAggregationInput {
+                fib_program_id,
+                proofs,
+            }),
+            |pipeline, &n| pipeline.child(ProgramProof::<FibProver, _>::new(n, fib_host.clone())),
+        )
+    }
+
+    #[test]
+    fn test_native() {
+        let receipt = fib_pipeline(&[5, 6, 7]).prove().unwrap();
+        let output =
+            FibAggregationProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert_eq!(output.fib_program_id, get_native_fib_host().program_id());
+        assert_eq!(output.outputs, [5, 8, 13]);
+        assert_eq!(output.sum, 26);
+
+        let receipt = fib_pipeline(&[]).prove().unwrap();
+        let output =
+            FibAggregationProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert!(output.outputs.is_empty());
+        assert_eq!(output.sum, 0);
+    }
+
+    #[test]
+    fn test_native_manual_wiring() {
+        // The same proof, wiring the `AggregationInput`s by hand
+        let fib_host = get_native_fib_host();
+        let proofs = [5, 6]
+            .iter()
+            .map(|n| {
+                let receipt = FibProver::prove(n, &fib_host).unwrap();
+                AggregationInput::new(
+                    receipt,
+                    fib_host.program_id(),
+                    fib_host.get_verification_key(),
+                )
+            })
+            .collect();
+        let input = FibAggregationInput {
+            fib_program_id: fib_host.program_id(),
+            proofs,
+        };
+
+        let receipt = FibAggregationProver::prove(&input, &get_native_host()).unwrap();
+        let output =
+            FibAggregationProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert_eq!(output.outputs, [5, 8]);
+        assert_eq!(output.sum, 13);
+    }
+
+    #[test]
+    fn test_native_missing_proof() {
+        // The guest panics when it is given fewer proofs than it expects to verify
+        let zkvm_input = ZkVmInputRecorder::new()
+            .write_borsh(&get_native_fib_host().program_id())
+            .unwrap()
+            .write_serde(&1u32)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let res = panic::catch_unwind(|| get_native_host().execute_recorded(&zkvm_input));
+        assert!(res.is_err());
+    }
+}
@@ -49,10 +49,22 @@ impl ZkVmProverPerf for FibProver {}
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
 
     use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
-    use strata_zkvm::ZkVmProver;
+    use strata_zkvm::{
+        AggregationInput, DifferentialHarness, DynZkVmHost, ExecutionOutcome, ProgramId,
+        ProgramProof, ProofCache, ProofPipeline, ProofType, PublicValues, ZkVm, ZkVmEnv, ZkVmError,
+        ZkVmHost, ZkVmInputBuilder, ZkVmInputRecorder, ZkVmInputResult, ZkVmProver, ZkVmResult,
+    };
 
     use super::process_fib;
     use crate::FibProver;
@@ -74,4 +86,277 @@ mod tests {
         let output = FibProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
         assert_eq!(output, 5);
     }
+
+    #[test]
+    fn test_native_proofs_are_not_cached() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let host = NativeHost {
+            process_proof: Arc::new(Box::new({
+                let runs = runs.clone();
+                move |zkvm: &NativeMachine| {
+                    runs.fetch_add(1, Ordering::Relaxed);
+                    process_fib(zkvm);
+                    Ok(())
+                }
+            })),
+        };
+        let dir = std::env::temp_dir().join(format!("fib-proof-cache-{}", std::process::id()));
+        let cache = ProofCache::new(dir);
+        cache.clear().unwrap();
+
+        let receipt = FibProver::prove_cached(&5, &host, &cache).unwrap();
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+        let output = FibProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert_eq!(output, 5);
+
+        // The native host has no program id, so its proofs are never cached, as they could be
+        // served to another native program
+        let proven = FibProver::prove_cached(&5, &host, &cache).unwrap();
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+        assert_eq!(proven, receipt);
+        assert!(!cache.dir().exists());
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_native_batch() {
+        let inputs: Vec<u32> = (0..8).collect();
+        let host = get_native_host();
+        let results = FibProver::prove_batch(&inputs, &host, 3);
+        let outputs: Vec<_> = results
+            .iter()
+            .map(|res| {
+                let receipt = res.as_ref().unwrap();
+                FibProver::process_output::<NativeHost>(receipt.public_values()).unwrap()
+            })
+            .collect();
+        assert_eq!(outputs, [0, 1, 1, 2, 3, 5, 8, 13]);
+
+        assert!(FibProver::prove_batch(&[], &host, 3).is_empty());
+    }
+
+    #[test]
+    fn test_native_batch_failures_and_parallelism() {
+        let running = Arc::new(AtomicU32::new(0));
+        let max_running = Arc::new(AtomicU32::new(0));
+        // Echoes its input after a delay, failing on 3 and panicking on 5
+        let host = NativeHost {
+            process_proof: Arc::new(Box::new({
+                let running = running.clone();
+                let max_running = max_running.clone();
+                move |zkvm: &NativeMachine| {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+
+                    let n: u32 = zkvm.read_serde();
+                    match n {
+                        3 => return Err(ZkVmError::ExecutionError("bad input".to_string())),
+                        5 => panic!("host bug"),
+                        _ => zkvm.commit_serde(&n),
+                    }
+                    Ok(())
+                }
+            })),
+        };
+
+        let inputs: Vec<u32> = (0..8).collect();
+        let results = FibProver::prove_batch(&inputs, &host, 2);
+        assert_eq!(results.len(), inputs.len());
+        for (n, res) in inputs.iter().zip(&results) {
+            match n {
+                3 => assert!(matches!(res, Err(ZkVmError::ExecutionError(_)))),
+                5 => assert!(matches!(res, Err(ZkVmError::Other(_)))),
+                _ => {
+                    let receipt = res.as_ref().unwrap();
+                    let output =
+                        FibProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+                    assert_eq!(output, *n as i32);
+                }
+            }
+        }
+        // Proofs may not overlap on a loaded machine, but never more than two run at once
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+    }
+
+    /// Sums the outputs of the fibonacci proofs it is given.
+    struct FibSumProver;
+
+    impl ZkVmProver for FibSumProver {
+        type Input = Vec<AggregationInput>;
+        type Output = i32;
+
+        fn proof_type() -> ProofType {
+            ProofType::Groth16
+        }
+
+        fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+        where
+            B: ZkVmInputBuilder<'a>,
+        {
+            let mut builder = B::new();
+            builder.write_serde(&(input.len() as u32))?;
+            for proof in input {
+                builder.write_proof(proof)?;
+            }
+            builder.build()
+        }
+
+        fn process_output<H>(public_values: &PublicValues) -> ZkVmResult<Self::Output>
+        where
+            H: ZkVmHost,
+        {
+            H::extract_serde_public_output(public_values)
+        }
+    }
+
+    fn get_native_sum_host() -> NativeHost {
+        NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                let n: u32 = zkvm.read_serde();
+                let sum: i32 = (0..n)
+                    .map(|_| zkvm.read_verified_serde::<i32>(&ProgramId::new([0; 8])))
+                    .sum();
+                zkvm.commit_serde(&sum);
+                Ok(())
+            })),
+        }
+    }
+
+    #[test]
+    fn test_native_pipeline() {
+        let fib = |n| ProgramProof::<FibProver, _>::new(n, get_native_host());
+        let pipeline =
+            ProofPipeline::<FibSumProver, _>::new(get_native_sum_host(), |proofs| proofs)
+                .child(fib(5))
+                .child(fib(6))
+                .child(fib(7));
+        assert_eq!(pipeline.len(), 3);
+
+        let receipt = pipeline.prove().unwrap();
+        let output = FibSumProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert_eq!(output, 5 + 8 + 13);
+
+        // Pipelines nest into trees of aggregations
+        let pipeline =
+            ProofPipeline::<FibSumProver, _>::new(get_native_sum_host(), |proofs| proofs)
+                .child(pipeline)
+                .child(fib(8));
+        let receipt = pipeline.prove().unwrap();
+        let output = FibSumProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert_eq!(output, 5 + 8 + 13 + 21);
+    }
+
+    #[test]
+    fn test_native_dyn_host() {
+        let mut hosts: HashMap<ZkVm, Box<dyn DynZkVmHost>> = HashMap::new();
+        hosts.insert(ZkVm::Native, Box::new(get_native_host()));
+
+        let input = FibProver::prepare_input::<ZkVmInputRecorder>(&5).unwrap();
+        let host = &hosts[&ZkVm::Native];
+
+        let public_values = host.execute_recorded(&input).unwrap();
+        let receipt = host.prove_recorded(&input, ProofType::Core).unwrap();
+        host.verify_receipt(&receipt).unwrap();
+
+        assert_eq!(receipt.public_values(), &public_values);
+        let output = FibProver::process_output::<NativeHost>(&public_values).unwrap();
+        assert_eq!(output, 5);
+    }
+
+    /// Commits the input shifted left by 16 bits, computed with `usize` like a guest would.
+    struct ShiftProver;
+
+    impl ZkVmProver for ShiftProver {
+        type Input = u32;
+        type Output = u64;
+
+        fn proof_type() -> ProofType {
+            ProofType::Core
+        }
+
+        fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+        where
+            B: ZkVmInputBuilder<'a>,
+        {
+            B::new().write_serde(input)?.build()
+        }
+
+        fn process_output<H>(public_values: &PublicValues) -> ZkVmResult<Self::Output>
+        where
+            H: ZkVmHost,
+        {
+            H::extract_serde_public_output(public_values)
+        }
+    }
+
+    /// A native host running the shift with the given `usize` width in bits.
+    fn get_native_shift_host(usize_bits: u32) -> NativeHost {
+        NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                let n: u32 = zkvm.read_serde();
+                let shifted = (n as u64) << 16;
+                zkvm.commit_serde(&(shifted & (u64::MAX >> (64 - usize_bits))));
+                Ok(())
+            })),
+        }
+    }
+
+    #[test]
+    fn test_native_differential() {
+        let harness = DifferentialHarness::<FibProver>::new(get_native_host())
+            .host(get_native_host())
+            .host(get_native_host());
+        let outcomes = harness.check(&10).unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0], ExecutionOutcome::Committed(55));
+
+        // Hosts agree on every input unless the `usize` width matters
+        let harness = DifferentialHarness::<ShiftProver>::new(get_native_shift_host(64))
+            .host(get_native_shift_host(64));
+        harness.fuzz(200, 0).unwrap();
+        let harness = DifferentialHarness::<ShiftProver>::new(get_native_shift_host(64))
+            .host(get_native_shift_host(32));
+        assert!(harness.check(&0xffff).is_ok());
+        let divergence = harness.fuzz(200, 0).unwrap_err();
+        assert_eq!(divergence.outcomes.len(), 2);
+        assert!(divergence.to_string().contains("native"));
+
+        // Aborting on every host is an agreement, panicking on one host only is not
+        let aborting = NativeHost {
+            process_proof: Arc::new(Box::new(move |_: &NativeMachine| {
+                Err(ZkVmError::ExecutionError("aborted".to_string()))
+            })),
+        };
+        let panicking = NativeHost {
+            process_proof: Arc::new(Box::new(move |_: &NativeMachine| panic!("guest panicked"))),
+        };
+        let harness = DifferentialHarness::<FibProver>::new(aborting).host(panicking.clone());
+        let outcomes = harness.check(&1).unwrap();
+        assert_eq!(
+            outcomes[1],
+            ExecutionOutcome::Panicked("guest panicked".to_string())
+        );
+
+        let harness = DifferentialHarness::<FibProver>::new(get_native_host()).host(panicking);
+        let divergence = harness.check(&1).unwrap_err();
+        assert_eq!(divergence.input, "1");
+        assert!(divergence.outcomes[1].1.is_aborted());
+
+        // Public values are compared once decoded, and undecodable ones agree with nothing
+        let truncating = NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                zkvm.commit_serde(&0u8);
+                Ok(())
+            })),
+        };
+        let harness = DifferentialHarness::<FibProver>::new(get_native_host()).host(truncating);
+        let divergence = harness.check(&1).unwrap_err();
+        assert!(matches!(
+            divergence.outcomes[1].1,
+            ExecutionOutcome::InvalidOutput(_)
+        ));
+    }
 }
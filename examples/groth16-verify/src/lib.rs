@@ -0,0 +1,229 @@
+use std::{fs, path::PathBuf};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_zkvm::{
+    Groth16Verifier, Proof, ProofReceipt, ProofType, ZkVm, ZkVmEnv, ZkVmError, ZkVmHost,
+    ZkVmInputResult, ZkVmProver, ZkVmResult,
+};
+
+/// Verifies a Groth16 proof with `verifier` and commits what was verified.
+///
+/// The verifier is chosen by the guest, typically the verifier of another zkVM, e.g. the SP1
+/// verifier in a Risc0 guest. The guest panics if the proof does not verify. The program
+/// commitment and the public values are committed, so whoever verifies the proof of this program
+/// must check that they belong to the program they expect.
+pub fn process_groth16_verification(zkvm: &impl ZkVmEnv, verifier: &impl Groth16Verifier) {
+    let input: Groth16VerificationInput = zkvm.read_borsh();
+    verifier
+        .verify_groth16(&input.proof, &input.verification_key, &input.public_values)
+        .expect("Groth16 proof verification failed");
+
+    zkvm.commit_borsh(&Groth16VerificationOutput {
+        verification_key: input.verification_key,
+        public_values: input.public_values,
+    });
+}
+
+/// A Groth16 proof to verify, in the form expected by [`Groth16Verifier::verify_groth16`].
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Groth16VerificationInput {
+    /// The seal of the Groth16 proof.
+    pub proof: Proof,
+    /// The 32-byte program commitment the proof is verified against.
+    pub verification_key: [u8; 32],
+    /// The raw public values of the proven program.
+    pub public_values: Vec<u8>,
+}
+
+impl Groth16VerificationInput {
+    /// Extracts the input from a receipt generated by `host` with [`ProofType::Groth16`].
+    pub fn from_receipt<H: ZkVmHost>(host: &H, receipt: &ProofReceipt) -> ZkVmResult<Self> {
+        let groth16_proof = H::extract_groth16_proof(receipt)?;
+        Ok(Self {
+            proof: groth16_proof.seal().clone(),
+            verification_key: host.get_groth16_verification_key(),
+            public_values: receipt.public_values().as_bytes().to_vec(),
+        })
+    }
+}
+
+/// The output committed by the verification program.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Groth16VerificationOutput {
+    /// The program commitment the proof was verified against.
+    pub verification_key: [u8; 32],
+    /// The public values of the verified proof.
+    pub public_values: Vec<u8>,
+}
+
+/// Returns the path of the [`Groth16VerificationInput`] fixture of the fibonacci program proven
+/// with `zkvm`.
+///
+/// The fixtures are written by the runner with `-p groth16-verify` and the real provers, which
+/// only prove fibonacci again when the fixture is missing. They are meant to be checked in, but
+/// are not generated yet: until then the runner fails and `test_fixtures_verify_in_execute_mode`
+/// is ignored.
+pub fn fixture_path(zkvm: ZkVm) -> PathBuf {
+    let name = match zkvm {
+        ZkVm::SP1 => "sp1",
+        ZkVm::Risc0 => "risc0",
+        ZkVm::Native => "native",
+    };
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(format!("{name}-fibonacci-groth16.bin"))
+}
+
+/// Loads the fixture of the fibonacci program proven with `zkvm`.
+pub fn load_fixture(zkvm: ZkVm) -> ZkVmResult<Groth16VerificationInput> {
+    let path = fixture_path(zkvm);
+    let bytes = fs::read(&path)
+        .map_err(|e| ZkVmError::Other(format!("failed to read {}: {e}", path.display())))?;
+    borsh::from_slice(&bytes)
+        .map_err(|e| ZkVmError::Other(format!("invalid fixture {}: {e}", path.display())))
+}
+
+/// Saves the fixture of the fibonacci program proven with `zkvm`.
+pub fn save_fixture(zkvm: ZkVm, input: &Groth16VerificationInput) -> ZkVmResult<()> {
+    let path = fixture_path(zkvm);
+    let bytes = borsh::to_vec(input).map_err(|e| ZkVmError::Other(e.to_string()))?;
+    fs::create_dir_all(path.parent().expect("fixture path has a parent"))
+        .and_then(|_| fs::write(&path, bytes))
+        .map_err(|e| ZkVmError::Other(format!("failed to write {}: {e}", path.display())))
+}
+
+pub struct Groth16VerifyProver;
+
+impl ZkVmProver for Groth16VerifyProver {
+    type Input = Groth16VerificationInput;
+    type Output = Groth16VerificationOutput;
+
+    fn proof_type() -> strata_zkvm::ProofType {
+        ProofType::Compressed
+    }
+
+    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+    where
+        B: strata_zkvm::ZkVmInputBuilder<'a>,
+    {
+        B::new().write_borsh(input)?.build()
+    }
+
+    fn process_output<H>(
+        public_values: &strata_zkvm::PublicValues,
+    ) -> strata_zkvm::ZkVmResult<Self::Output>
+    where
+        H: strata_zkvm::ZkVmHost,
+    {
+        H::extract_borsh_public_output(public_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use fibonacci::{process_fib, FibProver};
+    use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
+    use strata_risc0_adapter::Risc0Groth16Verifier;
+    use strata_sp1_adapter::SP1Groth16Verifier;
+    use strata_zkvm::PublicValues;
+
+    use super::*;
+
+    /// Stands in for the verifier of another zkVM, accepting the proofs whose seal is the
+    /// verification key followed by the public values.
+    struct StubVerifier;
+
+    impl Groth16Verifier for StubVerifier {
+        fn verify_groth16(
+            &self,
+            proof: &Proof,
+            verification_key: &[u8; 32],
+            public_values: &[u8],
+        ) -> ZkVmResult<()> {
+            if proof.as_bytes() == [verification_key.as_slice(), public_values].concat() {
+                Ok(())
+            } else {
+                Err(ZkVmError::ProofVerificationError(
+                    "invalid proof".to_string(),
+                ))
+            }
+        }
+    }
+
+    fn get_native_host() -> NativeHost {
+        NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_groth16_verification(zkvm, &StubVerifier);
+                Ok(())
+            })),
+        }
+    }
+
+    fn fib_input() -> Groth16VerificationInput {
+        let fib_host = NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_fib(zkvm);
+                Ok(())
+            })),
+        };
+        let receipt = FibProver::prove(&5, &fib_host).unwrap();
+        let verification_key = fib_host.get_groth16_verification_key();
+        let public_values = receipt.public_values().as_bytes().to_vec();
+        Groth16VerificationInput {
+            proof: Proof::new([verification_key.as_slice(), &public_values].concat()),
+            verification_key,
+            public_values,
+        }
+    }
+
+    #[test]
+    fn test_native() {
+        let input = fib_input();
+        let receipt = Groth16VerifyProver::prove(&input, &get_native_host()).unwrap();
+        let output =
+            Groth16VerifyProver::process_output::<NativeHost>(receipt.public_values()).unwrap();
+        assert_eq!(output.verification_key, input.verification_key);
+        assert_eq!(output.public_values, input.public_values);
+
+        let fib_output: i32 =
+            NativeHost::extract_serde_public_output(&PublicValues::new(output.public_values))
+                .unwrap();
+        assert_eq!(fib_output, 5);
+    }
+
+    #[test]
+    fn test_native_invalid_proof() {
+        let mut input = fib_input();
+        input.public_values[0] ^= 1;
+        let res =
+            std::panic::catch_unwind(|| Groth16VerifyProver::prove(&input, &get_native_host()));
+        assert!(res.is_err());
+    }
+
+    /// Verifies the fixture of `zkvm` with `verifier` in the guest function, in execute mode.
+    fn verify_fixture(zkvm: ZkVm, verifier: impl Groth16Verifier + Send + Sync + 'static) {
+        let input = load_fixture(zkvm).unwrap();
+        let host = NativeHost {
+            process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+                process_groth16_verification(zkvm, &verifier);
+                Ok(())
+            })),
+        };
+        let zkvm_input =
+            Groth16VerifyProver::prepare_input::<<NativeHost as ZkVmHost>::Input<'_>>(&input)
+                .unwrap();
+        let public_values = host.execute(zkvm_input).unwrap();
+        let output = Groth16VerifyProver::process_output::<NativeHost>(&public_values).unwrap();
+        assert_eq!(output.verification_key, input.verification_key);
+        assert_eq!(output.public_values, input.public_values);
+    }
+
+    #[test]
+    #[ignore = "the Groth16 fixtures are not generated yet, see `fixture_path`"]
+    fn test_fixtures_verify_in_execute_mode() {
+        verify_fixture(ZkVm::SP1, SP1Groth16Verifier);
+        verify_fixture(ZkVm::Risc0, Risc0Groth16Verifier);
+    }
+}
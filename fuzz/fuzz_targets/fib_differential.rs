@@ -0,0 +1,63 @@
+#![no_main]
+
+use std::sync::{Arc, LazyLock};
+
+use fibonacci::{process_fib, FibProver};
+use libfuzzer_sys::fuzz_target;
+use strata_native_zkvm_adapter::{NativeHost, NativeMachine};
+use strata_risc0_adapter::Risc0Host;
+use strata_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF;
+use strata_sp1_adapter::SP1Host;
+use strata_sp1_artifacts::FIBONACCI_ELF;
+use strata_zkvm::{
+    DifferentialHarness, ProofType, PublicValues, ZkVmHost, ZkVmInputBuilder, ZkVmInputResult,
+    ZkVmProver, ZkVmResult,
+};
+
+/// [`FibProver`] with a 16-bit input, which bounds the iterations of the guest so that each
+/// execution stays short.
+struct BoundedFibProver;
+
+impl ZkVmProver for BoundedFibProver {
+    type Input = u16;
+    type Output = i32;
+
+    fn proof_type() -> ProofType {
+        FibProver::proof_type()
+    }
+
+    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        B::new().write_serde(&u32::from(*input))?.build()
+    }
+
+    fn process_output<H>(public_values: &PublicValues) -> ZkVmResult<Self::Output>
+    where
+        H: ZkVmHost,
+    {
+        FibProver::process_output::<H>(public_values)
+    }
+}
+
+// The hosts are set up once, SP1 in particular takes seconds to set up a program
+static HARNESS: LazyLock<DifferentialHarness<BoundedFibProver>> = LazyLock::new(|| {
+    let native = NativeHost {
+        process_proof: Arc::new(Box::new(move |zkvm: &NativeMachine| {
+            process_fib(zkvm);
+            Ok(())
+        })),
+    };
+    DifferentialHarness::new(native)
+        .host(SP1Host::init(FIBONACCI_ELF))
+        .host(Risc0Host::init(GUEST_RISC0_FIBONACCI_ELF))
+});
+
+// libFuzzer aborts on any panic, so a guest panicking on the native host is reported as a crash
+// even when the zkVMs reject the input too.
+fuzz_target!(|data: &[u8]| {
+    if let Err(divergence) = HARNESS.check_bytes(data) {
+        panic!("{divergence}");
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use strata_native_zkvm_adapter::NativeHost;
+use strata_risc0_adapter::Risc0Host;
+use strata_sp1_adapter::SP1Host;
+use strata_zkvm::PublicValues;
+use strata_zkvm_fuzz::extract_public_outputs;
+
+fuzz_target!(|public_values: PublicValues| {
+    extract_public_outputs::<NativeHost>(&public_values);
+    extract_public_outputs::<SP1Host>(&public_values);
+    extract_public_outputs::<Risc0Host>(&public_values);
+});
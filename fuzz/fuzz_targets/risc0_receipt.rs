@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use strata_risc0_adapter::{to_canonical_groth16_proof, Risc0Host, Risc0ProofReceipt};
+use strata_zkvm::{ProofReceipt, ZkVmHost};
+
+fuzz_target!(|receipt: ProofReceipt| {
+    // A decoded receipt encodes back to a receipt with the same public values
+    if let Ok(risc0_receipt) = Risc0ProofReceipt::try_from(&receipt) {
+        let encoded = ProofReceipt::try_from(risc0_receipt).expect("decoded receipt must encode");
+        assert_eq!(encoded.public_values(), receipt.public_values());
+    }
+
+    if let Ok(groth16_proof) = Risc0Host::extract_groth16_proof(&receipt) {
+        let _ = to_canonical_groth16_proof(&groth16_proof, &[0; 32]);
+    }
+});
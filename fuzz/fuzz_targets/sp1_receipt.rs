@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use strata_sp1_adapter::{to_canonical_groth16_proof, SP1Host, SP1ProofReceipt};
+use strata_zkvm::{ProofReceipt, ZkVmHost};
+
+fuzz_target!(|receipt: ProofReceipt| {
+    // A decoded receipt encodes back to a receipt with the same public values
+    if let Ok(sp1_receipt) = SP1ProofReceipt::try_from(&receipt) {
+        let encoded = ProofReceipt::try_from(sp1_receipt).expect("decoded receipt must encode");
+        assert_eq!(encoded.public_values(), receipt.public_values());
+    }
+
+    if let Ok(groth16_proof) = SP1Host::extract_groth16_proof(&receipt) {
+        let _ = to_canonical_groth16_proof(&groth16_proof, &[0; 32]);
+    }
+});
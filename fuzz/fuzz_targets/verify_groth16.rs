@@ -0,0 +1,34 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use strata_zkvm::{Groth16Proof, Proof};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    proof: Proof,
+    verification_key: [u8; 32],
+    public_values: Vec<u8>,
+    public_values_digest: [u8; 32],
+}
+
+fuzz_target!(|input: Input| {
+    // Arbitrary bytes never verify, but must be rejected without panicking
+    assert!(strata_sp1_adapter::verify_groth16(
+        &input.proof,
+        &input.verification_key,
+        &input.public_values
+    )
+    .is_err());
+    assert!(strata_risc0_adapter::verify_groth16(
+        &input.proof,
+        &input.verification_key,
+        &input.public_values
+    )
+    .is_err());
+
+    let groth16_proof = Groth16Proof::new(input.proof, input.public_values_digest);
+    let _ = strata_sp1_adapter::to_canonical_groth16_proof(&groth16_proof, &input.verification_key);
+    let _ =
+        strata_risc0_adapter::to_canonical_groth16_proof(&groth16_proof, &input.verification_key);
+});
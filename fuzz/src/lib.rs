@@ -0,0 +1,32 @@
+//! Helpers shared by the fuzz targets.
+
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use strata_zkvm::{ProgramId, PublicValues, ZkVmHost};
+
+/// A nested output, exercising the length prefixes and the tags of the encodings.
+#[derive(Debug, Serialize, Deserialize, BorshDeserialize)]
+pub struct NestedOutput {
+    pub id: u64,
+    pub name: String,
+    pub values: Vec<Vec<u32>>,
+    pub tag: Option<[u8; 32]>,
+}
+
+/// Extracts the public values as outputs of several types with both encodings of `H`.
+///
+/// Malformed public values must be rejected with an error, not a panic or an allocation
+/// proportional to a length prefix instead of the input.
+pub fn extract_public_outputs<H: ZkVmHost>(public_values: &PublicValues) {
+    let _ = H::extract_serde_public_output::<u32>(public_values);
+    let _ = H::extract_serde_public_output::<i64>(public_values);
+    let _ = H::extract_serde_public_output::<Vec<u8>>(public_values);
+    let _ = H::extract_serde_public_output::<String>(public_values);
+    let _ = H::extract_serde_public_output::<NestedOutput>(public_values);
+
+    let _ = H::extract_borsh_public_output::<u32>(public_values);
+    let _ = H::extract_borsh_public_output::<Vec<u8>>(public_values);
+    let _ = H::extract_borsh_public_output::<String>(public_values);
+    let _ = H::extract_borsh_public_output::<ProgramId>(public_values);
+    let _ = H::extract_borsh_public_output::<NestedOutput>(public_values);
+}
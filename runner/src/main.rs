@@ -1,4 +1,4 @@
-use programs::{fibonacci, sha2, TestProgram};
+use programs::{aggregation, fibonacci, groth16_verify, sha2, TestProgram};
 
 mod args;
 mod programs;
@@ -22,6 +22,12 @@ fn main() {
             TestProgram::Sha2Chain => {
                 sha2::make_proofs();
             }
+            TestProgram::Aggregation => {
+                aggregation::make_proofs();
+            }
+            TestProgram::Groth16Verify => {
+                groth16_verify::make_proofs();
+            }
         }
     }
 }
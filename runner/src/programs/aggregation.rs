@@ -0,0 +1,49 @@
+use aggregation::{FibAggregationInput, FibAggregationProver};
+use fibonacci::FibProver;
+use strata_zkvm::{ProgramProof, ProofPipeline, ZkVmHost, ZkVmProver};
+
+fn prove_aggregation<H: ZkVmHost>(fib_host: H, aggregation_host: H) {
+    let fib_program_id = fib_host.program_id();
+    let pipeline = [5, 6, 7].into_iter().fold(
+        ProofPipeline::<FibAggregationProver, _>::new(aggregation_host, move |proofs| {
+            FibAggregationInput {
+                fib_program_id,
+                proofs,
+            }
+        }),
+        |pipeline, n| pipeline.child(ProgramProof::<FibProver, _>::new(n, fib_host.clone())),
+    );
+
+    let receipt = pipeline.prove().unwrap();
+    let output = FibAggregationProver::process_output::<H>(receipt.public_values()).unwrap();
+    assert_eq!(output.fib_program_id, fib_program_id);
+    assert_eq!(output.outputs, [5, 8, 13]);
+}
+
+#[cfg(feature = "sp1")]
+fn sp1_prove_aggregation() {
+    use strata_sp1_adapter::SP1Host;
+    use strata_sp1_artifacts::{AGGREGATION_ELF, FIBONACCI_ELF};
+    prove_aggregation(
+        SP1Host::init(&FIBONACCI_ELF),
+        SP1Host::init(&AGGREGATION_ELF),
+    );
+}
+
+#[cfg(feature = "risc0")]
+fn risc0_prove_aggregation() {
+    use strata_risc0_adapter::Risc0Host;
+    use strata_risc0_artifacts::{GUEST_RISC0_AGGREGATION_ELF, GUEST_RISC0_FIBONACCI_ELF};
+    prove_aggregation(
+        Risc0Host::init(&GUEST_RISC0_FIBONACCI_ELF),
+        Risc0Host::init(&GUEST_RISC0_AGGREGATION_ELF),
+    );
+}
+
+pub fn make_proofs() {
+    #[cfg(feature = "risc0")]
+    risc0_prove_aggregation();
+
+    #[cfg(feature = "sp1")]
+    sp1_prove_aggregation();
+}
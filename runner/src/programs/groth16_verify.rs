@@ -0,0 +1,95 @@
+use fibonacci::FibProver;
+use groth16_verify::{
+    fixture_path, load_fixture, save_fixture, Groth16VerificationInput, Groth16VerificationOutput,
+    Groth16VerifyProver,
+};
+use strata_zkvm::{ProofType, ZkVm, ZkVmHost, ZkVmProver};
+
+/// Returns the Groth16 proof of fibonacci generated with `zkvm`, from its fixture if it exists.
+///
+/// Otherwise fibonacci is proven with `fib_host` and the fixture is written. Mock provers do not
+/// generate verifiable Groth16 proofs, so they never write fixtures.
+///
+/// # Panics
+///
+/// Panics if the fixture is invalid, or if it is missing and cannot be generated, so that a
+/// missing fixture never silently skips the verification.
+fn groth16_fixture<H: ZkVmHost>(
+    zkvm: ZkVm,
+    fib_host: Option<&H>,
+    mock: bool,
+) -> Groth16VerificationInput {
+    let path = fixture_path(zkvm);
+    if path.exists() {
+        return load_fixture(zkvm).unwrap();
+    }
+    let Some(fib_host) = fib_host.filter(|_| !mock) else {
+        panic!(
+            "missing {zkvm:?} Groth16 fixture {}: generate it with the {zkvm:?} prover, without \
+             its mock feature",
+            path.display()
+        );
+    };
+
+    let zkvm_input = FibProver::prepare_input::<H::Input<'_>>(&5).unwrap();
+    let receipt = fib_host.prove(zkvm_input, ProofType::Groth16).unwrap();
+    let input = Groth16VerificationInput::from_receipt(fib_host, &receipt).unwrap();
+    save_fixture(zkvm, &input).unwrap();
+    input
+}
+
+/// Verifies the Groth16 proof inside the guest of `host` in execute mode.
+fn verify_in_guest<H: ZkVmHost>(input: &Groth16VerificationInput, host: &H) {
+    let zkvm_input = Groth16VerifyProver::prepare_input::<H::Input<'_>>(input).unwrap();
+    let public_values = host.execute(zkvm_input).unwrap();
+    let output = Groth16VerifyProver::process_output::<H>(&public_values).unwrap();
+    assert_eq!(
+        output,
+        Groth16VerificationOutput {
+            verification_key: input.verification_key,
+            public_values: input.public_values.clone(),
+        }
+    );
+}
+
+/// Verifies the SP1 Groth16 proof of fibonacci inside Risc0.
+#[cfg(feature = "risc0")]
+fn verify_sp1_in_risc0() {
+    use strata_risc0_adapter::Risc0Host;
+    use strata_risc0_artifacts::GUEST_RISC0_GROTH16_VERIFY_ELF;
+
+    #[cfg(feature = "sp1")]
+    let fib_host = Some(strata_sp1_adapter::SP1Host::init(
+        strata_sp1_artifacts::FIBONACCI_ELF,
+    ));
+    #[cfg(not(feature = "sp1"))]
+    let fib_host: Option<Risc0Host> = None;
+
+    let input = groth16_fixture(ZkVm::SP1, fib_host.as_ref(), cfg!(feature = "sp1-mock"));
+    verify_in_guest(&input, &Risc0Host::init(GUEST_RISC0_GROTH16_VERIFY_ELF));
+}
+
+/// Verifies the Risc0 Groth16 proof of fibonacci inside SP1.
+#[cfg(feature = "sp1")]
+fn verify_risc0_in_sp1() {
+    use strata_sp1_adapter::SP1Host;
+    use strata_sp1_artifacts::GROTH16_VERIFY_ELF;
+
+    #[cfg(feature = "risc0")]
+    let fib_host = Some(strata_risc0_adapter::Risc0Host::init(
+        strata_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF,
+    ));
+    #[cfg(not(feature = "risc0"))]
+    let fib_host: Option<SP1Host> = None;
+
+    let input = groth16_fixture(ZkVm::Risc0, fib_host.as_ref(), cfg!(feature = "risc0-mock"));
+    verify_in_guest(&input, &SP1Host::init(GROTH16_VERIFY_ELF));
+}
+
+pub fn make_proofs() {
+    #[cfg(feature = "risc0")]
+    verify_sp1_in_risc0();
+
+    #[cfg(feature = "sp1")]
+    verify_risc0_in_sp1();
+}
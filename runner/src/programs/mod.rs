@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
+pub mod aggregation;
 pub mod fibonacci;
+pub mod groth16_verify;
 pub mod sha2;
 
 #[derive(Debug)]
@@ -8,6 +10,8 @@ pub mod sha2;
 pub enum TestProgram {
     Fibonacci,
     Sha2Chain,
+    Aggregation,
+    Groth16Verify,
 }
 
 impl FromStr for TestProgram {
@@ -17,6 +21,8 @@ impl FromStr for TestProgram {
         match s.to_lowercase().as_str() {
             "fibonacci" => Ok(TestProgram::Fibonacci),
             "sha2-chain" => Ok(TestProgram::Sha2Chain),
+            "aggregation" => Ok(TestProgram::Aggregation),
+            "groth16-verify" => Ok(TestProgram::Groth16Verify),
             // Add more matches
             _ => Err(format!("unknown program: {}", s)),
         }
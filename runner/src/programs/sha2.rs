@@ -1,32 +1,39 @@
+use std::collections::HashMap;
+
 use sha2_chain::ShaChainProver;
-use strata_zkvm::{ProofReceipt, ZkVmHost, ZkVmProver};
+use strata_zkvm::{DynZkVmHost, ZkVm, ZkVmInputRecorder, ZkVmProver};
 
-fn prove(host: &impl ZkVmHost) -> ProofReceipt {
-    let input = 5;
-    ShaChainProver::prove(&input, host).unwrap()
-}
+fn hosts() -> HashMap<ZkVm, Box<dyn DynZkVmHost>> {
+    let mut hosts: HashMap<ZkVm, Box<dyn DynZkVmHost>> = HashMap::new();
 
-#[cfg(feature = "sp1")]
-fn sp1_prove() -> ProofReceipt {
-    use strata_sp1_adapter::SP1Host;
-    use strata_sp1_artifacts::SHA2_CHAIN_ELF;
-    let host = SP1Host::init(&SHA2_CHAIN_ELF);
-    prove(&host)
-}
+    #[cfg(feature = "sp1")]
+    {
+        use strata_sp1_adapter::SP1Host;
+        use strata_sp1_artifacts::SHA2_CHAIN_ELF;
+        hosts.insert(ZkVm::SP1, Box::new(SP1Host::init(&SHA2_CHAIN_ELF)));
+    }
 
-#[cfg(feature = "risc0")]
-fn risc0_prove() -> ProofReceipt {
-    use strata_risc0_adapter::Risc0Host;
-    use strata_risc0_artifacts::GUEST_RISC0_SHA2_CHAIN_ELF;
-    let host = Risc0Host::init(&GUEST_RISC0_SHA2_CHAIN_ELF);
-    prove(&host)
+    #[cfg(feature = "risc0")]
+    {
+        use strata_risc0_adapter::Risc0Host;
+        use strata_risc0_artifacts::GUEST_RISC0_SHA2_CHAIN_ELF;
+        hosts.insert(
+            ZkVm::Risc0,
+            Box::new(Risc0Host::init(&GUEST_RISC0_SHA2_CHAIN_ELF)),
+        );
+    }
+
+    hosts
 }
 
 pub fn make_proofs() {
     // TODO: add reports
-    #[cfg(feature = "risc0")]
-    let _ = risc0_prove();
+    let input = 5;
+    let input = ShaChainProver::prepare_input::<ZkVmInputRecorder>(&input).unwrap();
 
-    #[cfg(feature = "sp1")]
-    let _ = sp1_prove();
+    for host in hosts().values() {
+        let _ = host
+            .prove_recorded(&input, ShaChainProver::proof_type())
+            .unwrap();
+    }
 }